@@ -15,10 +15,20 @@ pub enum SpriteEnum {
     TankDefaultTurret,
     TankClassic,
     TankClassicTurret,
+    TankGreen,
+    TankGreenTurret,
+    FlagBrown,
+    FlagGrey,
+    FlagGreen,
     Tree,
     TreeTrunk1,
     TreeLeaves1,
     Bullet,
+    Mine,
+    HealthPack,
+    SpeedPickup,
+    DamagePickup,
+    SkillPickup,
     Background,
 }
 
@@ -28,10 +38,20 @@ pub static SPRITE_PATH_MAP: Lazy<HashMap<SpriteEnum, &str>> = Lazy::new(||
         (TankDefaultTurret, "tank_default_turret.png"),
         (TankClassic, "tank_classic.png"),
         (TankClassicTurret, "tank_classic_turret.png"),
+        (TankGreen, "tank_green.png"),
+        (TankGreenTurret, "tank_green_turret.png"),
+        (FlagBrown, "flag_brown.png"),
+        (FlagGrey, "flag_grey.png"),
+        (FlagGreen, "flag_green.png"),
         (Tree, "tree.png"),
         (TreeTrunk1, "tree_trunk_1.png"),
         (TreeLeaves1, "tree_leaves_1.png"),
         (Bullet, "bullet.png"),
+        (Mine, "mine.png"),
+        (HealthPack, "health_pack.png"),
+        (SpeedPickup, "speed_pickup.png"),
+        (DamagePickup, "damage_pickup.png"),
+        (SkillPickup, "skill_pickup.png"),
         (Background, "background.png")
     ])
 );
@@ -7,7 +7,7 @@ use bevy::prelude::{IntoSystemAppConfigs, IntoSystemConfig, OnEnter, OnUpdate};
 use resources::*;
 use crate::AppState;
 use crate::asset_loader::components::SpriteEnum;
-use crate::asset_loader::system::{check_assets_loaded, load_fonts, load_sprites};
+use crate::asset_loader::system::{check_assets_loaded, load_fonts, load_skills, load_sprites, load_weapons};
 
 pub struct AssetLoaderPlugin;
 
@@ -16,13 +16,17 @@ impl Plugin for AssetLoaderPlugin {
         app
             .insert_resource(SpriteAssets::default())
             .insert_resource(FontAssets::default())
+            .insert_resource(WeaponAssets::default())
+            .insert_resource(SkillAssets::default())
             .register_type::<SpriteEnum>()
             .insert_resource(AssetsLoading::default())
             .add_event::<AssetsLoadedEvent>()
             .add_systems(
                 (
                     load_sprites,
-                    load_fonts
+                    load_fonts,
+                    load_weapons,
+                    load_skills,
                 ).in_schedule(OnEnter(AppState::Loading))
             )
             .add_system(check_assets_loaded.in_set(OnUpdate(AppState::Loading)));
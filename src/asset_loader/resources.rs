@@ -1,7 +1,9 @@
-use bevy::asset::{AssetServer, Handle, HandleUntyped};
+use bevy::asset::{Assets, AssetServer, Handle, HandleUntyped};
 use bevy::prelude::{Font, Image, Resource};
 use std::collections::HashMap;
 use crate::asset_loader::components::{FONT_PATH_MAP, FontEnum, SpriteEnum};
+use crate::simulation::server_sim::skill::{SkillDefinition, SkillDefinitions};
+use crate::simulation::server_sim::weapon::{WeaponDefinition, WeaponDefinitions};
 
 #[derive(Default, Resource)]
 pub struct SpriteAssets {
@@ -45,5 +47,31 @@ impl FontAssets {
     }
 }
 
+pub const WEAPONS_PATH: &str = "weapons/default.weapons.ron";
+
+#[derive(Default, Resource)]
+pub struct WeaponAssets {
+    pub handle: Handle<WeaponDefinitions>,
+}
+
+impl WeaponAssets {
+    pub fn get<'a>(&self, definitions: &'a Assets<WeaponDefinitions>, name: &str) -> Option<&'a WeaponDefinition> {
+        definitions.get(&self.handle)?.get(name)
+    }
+}
+
 #[derive(Default, Resource)]
 pub struct AssetsLoading(pub Vec<HandleUntyped>);
+
+pub const SKILLS_PATH: &str = "skills/default.skills.ron";
+
+#[derive(Default, Resource)]
+pub struct SkillAssets {
+    pub handle: Handle<SkillDefinitions>,
+}
+
+impl SkillAssets {
+    pub fn get<'a>(&self, definitions: &'a Assets<SkillDefinitions>, name: &str) -> Option<&'a SkillDefinition> {
+        definitions.get(&self.handle)?.get(name)
+    }
+}
@@ -2,7 +2,7 @@ use bevy::prelude::{Commands, EventWriter, Res, ResMut};
 use bevy::asset::{AssetServer, LoadState};
 use crate::asset_loader::AssetsLoadedEvent;
 use crate::asset_loader::components::{SPRITE_PATH_MAP};
-use crate::asset_loader::resources::{AssetsLoading, FontAssets, SpriteAssets};
+use crate::asset_loader::resources::{AssetsLoading, FontAssets, SKILLS_PATH, SkillAssets, SpriteAssets, WEAPONS_PATH, WeaponAssets};
 
 pub fn load_sprites(
     mut game_assets: ResMut<SpriteAssets>,
@@ -30,6 +30,24 @@ pub fn load_fonts(
     })
 }
 
+pub fn load_weapons(
+    mut weapon_assets: ResMut<WeaponAssets>,
+    asset_server: Res<AssetServer>,
+    mut loading: ResMut<AssetsLoading>,
+) {
+    weapon_assets.handle = asset_server.load(WEAPONS_PATH);
+    loading.0.push(weapon_assets.handle.clone_untyped());
+}
+
+pub fn load_skills(
+    mut skill_assets: ResMut<SkillAssets>,
+    asset_server: Res<AssetServer>,
+    mut loading: ResMut<AssetsLoading>,
+) {
+    skill_assets.handle = asset_server.load(SKILLS_PATH);
+    loading.0.push(skill_assets.handle.clone_untyped());
+}
+
 pub fn check_assets_loaded(
     mut commands: Commands,
     mut evt_wr: EventWriter<AssetsLoadedEvent>,
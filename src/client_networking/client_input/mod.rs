@@ -5,6 +5,7 @@ use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 use crate::client_networking::client_input::systems::*;
 use crate::ClientSet::ClientSend;
+use crate::simulation::server_sim::skill::SKILL_SLOTS;
 
 pub struct ClientInputPlugin;
 
@@ -26,4 +27,10 @@ pub struct ClientInput {
     pub movement: Vec2,
     pub mouse_pos: Vec2,
     pub fire_bullet: bool,
-}
\ No newline at end of file
+    /// Whether the input of every skill slot is held.
+    pub skills: [bool; SKILL_SLOTS],
+}
+
+/// What the HUD calls the input of every skill slot, they're read in `keyboard_events` and
+/// `mouse_click`.
+pub const SKILL_BINDINGS: [&str; SKILL_SLOTS] = ["Shift", "Space", "Right click", "E"];
\ No newline at end of file
@@ -1,15 +1,17 @@
-use bevy::prelude::{Camera, GlobalTransform, KeyCode, MouseButton, Query, Res, ResMut, Window, With};
+use bevy::prelude::{Camera, EventWriter, GlobalTransform, KeyCode, MouseButton, Query, Res, ResMut, Window, With};
 use bevy::math::Vec2;
 use bevy::input::Input;
 use bevy::utils::HashMap;
 use bevy::window::PrimaryWindow;
 use crate::client_networking::client_input::ClientInput;
+use crate::client_networking::{DropMineEvent, ReloadEvent};
 use crate::display::camera::MainCamera;
 use crate::utils::ndc::{screen_to_world, ScreenSize};
 
 pub fn keyboard_events(
     keys: Res<Input<KeyCode>>,
     mut input: ResMut<ClientInput>,
+    (mut mine_writer, mut reload_writer): (EventWriter<DropMineEvent>, EventWriter<ReloadEvent>),
 ) {
     let key_to_input_map: HashMap<KeyCode, [f32; 2]> = HashMap::from([
         (KeyCode::W, [0., 1.]),
@@ -41,6 +43,16 @@ pub fn keyboard_events(
     if let Some(input_update) = input_update {
         input.movement += input_update;
     }
+
+    if keys.just_pressed(KeyCode::Q) {
+        mine_writer.send(DropMineEvent);
+    }
+    if keys.just_pressed(KeyCode::R) {
+        reload_writer.send(ReloadEvent);
+    }
+    input.skills[0] = keys.pressed(KeyCode::LShift);
+    input.skills[1] = keys.pressed(KeyCode::Space);
+    input.skills[3] = keys.pressed(KeyCode::E);
 }
 
 pub fn mouse_position(
@@ -63,5 +75,6 @@ pub fn mouse_click(
     mut input: ResMut<ClientInput>,
     button: ResMut<Input<MouseButton>>,
 ) {
-    input.fire_bullet = button.just_pressed(MouseButton::Left);
+    input.fire_bullet = button.pressed(MouseButton::Left);
+    input.skills[2] = button.pressed(MouseButton::Right);
 }
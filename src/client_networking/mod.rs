@@ -1,7 +1,7 @@
 mod systems;
 mod client_input;
 
-pub use crate::client_networking::client_input::ClientInput;
+pub use crate::client_networking::client_input::{ClientInput, SKILL_BINDINGS};
 
 use bevy::prelude::*;
 use bevy::app::{App, Plugin};
@@ -13,6 +13,17 @@ use crate::client_networking::systems::*;
 use crate::utils::networking::messages::{PhysicsObjData, PlayerId};
 use crate::simulation::PlayerData;
 use crate::simulation::ObjectId;
+use crate::simulation::map::MapSource;
+use crate::simulation::server_sim::game_mode::MatchOutcome;
+use crate::simulation::server_sim::game_mode::battle_royale::BattleRoyaleZone;
+use crate::simulation::server_sim::game_mode::capture_the_flag::FlagEvent;
+use crate::simulation::server_sim::game_mode::king_of_the_hill::ControlPoints;
+use crate::simulation::server_sim::game_mode::elimination::RoundState;
+use crate::simulation::server_sim::game_mode::survival::WaveState;
+use crate::simulation::team::Team;
+use crate::simulation::server_sim::weapon::WeaponStatus;
+use crate::simulation::server_sim::leveling::Stat;
+use crate::simulation::server_sim::skill::{ActiveShields, SkillStatus};
 
 pub struct ClientNetworkingPlugin;
 
@@ -32,10 +43,35 @@ impl Plugin for ClientNetworkingPlugin {
             .add_event::<RecvPhysObjUpdateEvent>()
             .add_event::<RecvPlayerDataUpdateEvent>()
             .add_event::<RecvTurretUpdateEvent>()
+            .add_event::<RecvMapInitEvent>()
+            .add_event::<RecvZoneUpdateEvent>()
+            .add_event::<RecvGameModeEvent>()
+            .add_event::<RecvMatchEndEvent>()
+            .add_event::<RecvFlagEvent>()
+            .add_event::<RecvControlPointsEvent>()
+            .add_event::<RecvRoundEvent>()
+            .add_event::<RecvWaveEvent>()
+            .add_event::<RecvBulletBounceEvent>()
+            .add_event::<RecvExplosionEvent>()
+            .add_event::<RecvBeamEvent>()
+            .add_event::<RecvWeaponStatusEvent>()
+            .add_event::<RecvSkillStatusEvent>()
+            .add_event::<RecvShieldsEvent>()
+            .add_event::<RecvShieldBlockEvent>()
+            .add_event::<ChooseTeamEvent>()
+            .add_event::<ChooseUpgradeEvent>()
+            .add_event::<ChooseWeaponEvent>()
+            .add_event::<DropMineEvent>()
+            .add_event::<ReloadEvent>()
             .add_systems(
                 (
                     client_recv.in_set(ClientReceive),
-                    client_send.in_set(ClientSend)
+                    client_send.in_set(ClientSend),
+                    send_team_choice.in_set(ClientSend),
+                    send_upgrade_choice.in_set(ClientSend),
+                    send_weapon_choice.in_set(ClientSend),
+                    send_mine_drop.in_set(ClientSend),
+                    send_reload.in_set(ClientSend),
                 )
             );
     }
@@ -90,6 +126,93 @@ pub struct RecvTurretUpdateEvent {
     pub rotation: Quat,
 }
 
+pub struct RecvMapInitEvent {
+    pub source: MapSource,
+}
+
+pub struct RecvZoneUpdateEvent {
+    pub zone: BattleRoyaleZone,
+}
+
+pub struct RecvGameModeEvent {
+    pub name: String,
+    pub teams: Vec<Team>,
+}
+
+pub struct RecvMatchEndEvent {
+    pub outcome: MatchOutcome,
+}
+
+pub struct RecvFlagEvent {
+    pub event: FlagEvent,
+}
+
+pub struct RecvControlPointsEvent {
+    pub control_points: ControlPoints,
+}
+
+pub struct RecvRoundEvent {
+    pub rounds: RoundState,
+}
+
+pub struct RecvWaveEvent {
+    pub waves: WaveState,
+}
+
+pub struct RecvBulletBounceEvent {
+    pub object_id: ObjectId,
+    pub position: Vec2,
+    pub velocity: Vec2,
+}
+
+pub struct RecvExplosionEvent {
+    pub position: Vec2,
+    pub radius: f32,
+}
+
+pub struct RecvBeamEvent {
+    pub start: Vec2,
+    pub end: Vec2,
+}
+
+pub struct RecvWeaponStatusEvent {
+    pub status: WeaponStatus,
+}
+
+pub struct RecvSkillStatusEvent {
+    pub status: SkillStatus,
+}
+
+pub struct RecvShieldsEvent {
+    pub shields: ActiveShields,
+}
+
+pub struct RecvShieldBlockEvent {
+    pub position: Vec2,
+}
+
+/// Asks the server to move you to another team.
+pub struct ChooseTeamEvent {
+    pub team: Team,
+}
+
+/// Asks the server to spend one of your points on a stat.
+pub struct ChooseUpgradeEvent {
+    pub stat: Stat,
+}
+
+/// Asks the server for a different weapon, used from your next spawn on.
+pub struct ChooseWeaponEvent {
+    pub weapon: String,
+}
+
+/// Asks the server to drop a mine. Sent reliably rather than with the input, so a press is
+/// never lost or repeated.
+pub struct DropMineEvent;
+
+/// Asks the server to reload your weapon early, sent reliably for the same reason.
+pub struct ReloadEvent;
+
 #[derive(Resource)]
 pub struct ClientId(pub PlayerId);
 
@@ -97,5 +220,16 @@ pub struct ClientId(pub PlayerId);
 pub enum ClientMessage {
     InputMessage {
         input: ClientInput
-    }
+    },
+    ChooseTeam {
+        team: Team
+    },
+    UpgradeStat {
+        stat: Stat
+    },
+    ChooseWeapon {
+        weapon: String
+    },
+    DropMine,
+    Reload,
 }
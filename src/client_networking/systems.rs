@@ -1,7 +1,7 @@
-use bevy::prelude::{EventWriter, Res, ResMut};
+use bevy::prelude::{EventReader, EventWriter, Res, ResMut};
 use bevy_quinnet::client::Client;
 use bevy_quinnet::shared::channel::ChannelId;
-use crate::client_networking::{ClientMessage, RecvHealthUpdateEvent, RecvMatchTimeEvent, RecvObjectDespawnEvent, RecvPhysObjUpdateEvent, RecvPlayerConnectEvent, RecvPlayerDataUpdateEvent, RecvPlayerLeaveEvent, RecvPlayerSpawnEvent, RecvTurretUpdateEvent, RecvYouConnectEvent};
+use crate::client_networking::{ChooseTeamEvent, ChooseUpgradeEvent, ChooseWeaponEvent, ClientMessage, DropMineEvent, ReloadEvent, RecvHealthUpdateEvent, RecvMatchTimeEvent, RecvObjectDespawnEvent, RecvPhysObjUpdateEvent, RecvPlayerConnectEvent, RecvPlayerDataUpdateEvent, RecvPlayerLeaveEvent, RecvPlayerSpawnEvent, RecvTurretUpdateEvent, RecvYouConnectEvent, RecvMapInitEvent, RecvZoneUpdateEvent, RecvGameModeEvent, RecvMatchEndEvent, RecvFlagEvent, RecvControlPointsEvent, RecvRoundEvent, RecvWaveEvent, RecvBulletBounceEvent, RecvExplosionEvent, RecvBeamEvent, RecvWeaponStatusEvent, RecvSkillStatusEvent, RecvShieldsEvent, RecvShieldBlockEvent};
 use crate::client_networking::client_input::ClientInput;
 use crate::utils::networking::messages::*;
 
@@ -16,6 +16,66 @@ pub fn client_send(
         }).unwrap();
 }
 
+pub fn send_team_choice(
+    mut events: EventReader<ChooseTeamEvent>,
+    client: Res<Client>,
+) {
+    events.iter().for_each(|e| {
+        client.connection().send_message_on(
+            ChannelId::UnorderedReliable,
+            ClientMessage::ChooseTeam { team: e.team },
+        ).unwrap();
+    });
+}
+
+pub fn send_upgrade_choice(
+    mut events: EventReader<ChooseUpgradeEvent>,
+    client: Res<Client>,
+) {
+    events.iter().for_each(|e| {
+        client.connection().send_message_on(
+            ChannelId::UnorderedReliable,
+            ClientMessage::UpgradeStat { stat: e.stat },
+        ).unwrap();
+    });
+}
+
+pub fn send_weapon_choice(
+    mut events: EventReader<ChooseWeaponEvent>,
+    client: Res<Client>,
+) {
+    events.iter().for_each(|e| {
+        client.connection().send_message_on(
+            ChannelId::UnorderedReliable,
+            ClientMessage::ChooseWeapon { weapon: e.weapon.clone() },
+        ).unwrap();
+    });
+}
+
+pub fn send_mine_drop(
+    mut events: EventReader<DropMineEvent>,
+    client: Res<Client>,
+) {
+    events.iter().for_each(|_| {
+        client.connection().send_message_on(
+            ChannelId::UnorderedReliable,
+            ClientMessage::DropMine,
+        ).unwrap();
+    });
+}
+
+pub fn send_reload(
+    mut events: EventReader<ReloadEvent>,
+    client: Res<Client>,
+) {
+    events.iter().for_each(|_| {
+        client.connection().send_message_on(
+            ChannelId::UnorderedReliable,
+            ClientMessage::Reload,
+        ).unwrap();
+    });
+}
+
 pub fn client_recv(
     mut client: ResMut<Client>,
     (mut you_joined_event, mut join_event, mut leave_event):
@@ -24,19 +84,43 @@ pub fn client_recv(
         EventWriter<RecvPlayerConnectEvent>,
         EventWriter<RecvPlayerLeaveEvent>,
     ),
-    (mut despawn_event, mut spawn_event):
+    (mut despawn_event, mut spawn_event, mut bullet_bounce_event, mut explosion_event):
     (
         EventWriter<RecvObjectDespawnEvent>,
-        EventWriter<RecvPlayerSpawnEvent>
+        EventWriter<RecvPlayerSpawnEvent>,
+        EventWriter<RecvBulletBounceEvent>,
+        EventWriter<RecvExplosionEvent>,
     ),
-    (mut phys_update_event, mut health_update_event, mut player_data_event):
+    (mut phys_update_event, mut health_update_event, mut player_data_event, mut weapon_status_event):
     (
         EventWriter<RecvPhysObjUpdateEvent>,
         EventWriter<RecvHealthUpdateEvent>,
-        EventWriter<RecvPlayerDataUpdateEvent>
+        EventWriter<RecvPlayerDataUpdateEvent>,
+        EventWriter<RecvWeaponStatusEvent>,
+    ),
+    (mut match_time_event, mut turr_update_event, mut round_event, mut wave_event, mut skill_status_event):
+    (
+        EventWriter<RecvMatchTimeEvent>,
+        EventWriter<RecvTurretUpdateEvent>,
+        EventWriter<RecvRoundEvent>,
+        EventWriter<RecvWaveEvent>,
+        EventWriter<RecvSkillStatusEvent>,
+    ),
+    (mut map_init_event, mut zone_update_event, mut control_points_event, mut shields_event, mut shield_block_event):
+    (
+        EventWriter<RecvMapInitEvent>,
+        EventWriter<RecvZoneUpdateEvent>,
+        EventWriter<RecvControlPointsEvent>,
+        EventWriter<RecvShieldsEvent>,
+        EventWriter<RecvShieldBlockEvent>,
+    ),
+    (mut game_mode_event, mut match_end_event, mut flag_event, mut beam_event):
+    (
+        EventWriter<RecvGameModeEvent>,
+        EventWriter<RecvMatchEndEvent>,
+        EventWriter<RecvFlagEvent>,
+        EventWriter<RecvBeamEvent>,
     ),
-    mut match_time_event: EventWriter<RecvMatchTimeEvent>,
-    mut turr_update_event: EventWriter<RecvTurretUpdateEvent>,
 ) {
     while let Ok(Some(message)) = client.connection_mut().receive_message::<ServerMessage>() {
         match message {
@@ -81,6 +165,51 @@ pub fn client_recv(
                     turr_update_event.send(RecvTurretUpdateEvent { parent_id, rotation });
                 })
             }
+            ServerMessage::MapInit { source } => {
+                map_init_event.send(RecvMapInitEvent { source });
+            }
+            ServerMessage::ZoneUpdate { zone } => {
+                zone_update_event.send(RecvZoneUpdateEvent { zone });
+            }
+            ServerMessage::GameModeInit { name, teams } => {
+                game_mode_event.send(RecvGameModeEvent { name, teams });
+            }
+            ServerMessage::MatchEnd { outcome } => {
+                match_end_event.send(RecvMatchEndEvent { outcome });
+            }
+            ServerMessage::FlagFeed { event } => {
+                flag_event.send(RecvFlagEvent { event });
+            }
+            ServerMessage::ControlPointsUpdate { control_points } => {
+                control_points_event.send(RecvControlPointsEvent { control_points });
+            }
+            ServerMessage::RoundUpdate { rounds } => {
+                round_event.send(RecvRoundEvent { rounds });
+            }
+            ServerMessage::WaveUpdate { waves } => {
+                wave_event.send(RecvWaveEvent { waves });
+            }
+            ServerMessage::BulletBounce { object_id, position, velocity } => {
+                bullet_bounce_event.send(RecvBulletBounceEvent { object_id, position, velocity });
+            }
+            ServerMessage::Explosion { position, radius } => {
+                explosion_event.send(RecvExplosionEvent { position, radius });
+            }
+            ServerMessage::Beam { start, end } => {
+                beam_event.send(RecvBeamEvent { start, end });
+            }
+            ServerMessage::WeaponStatusUpdate { status } => {
+                weapon_status_event.send(RecvWeaponStatusEvent { status });
+            }
+            ServerMessage::SkillStatusUpdate { status } => {
+                skill_status_event.send(RecvSkillStatusEvent { status });
+            }
+            ServerMessage::ShieldsUpdate { shields } => {
+                shields_event.send(RecvShieldsEvent { shields });
+            }
+            ServerMessage::ShieldBlock { position } => {
+                shield_block_event.send(RecvShieldBlockEvent { position });
+            }
         }
     }
 }
\ No newline at end of file
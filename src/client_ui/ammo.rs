@@ -0,0 +1,74 @@
+use bevy::app::App;
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use bevy_egui::{egui, EguiContexts};
+use bevy_egui::egui::{Color32, ProgressBar, RichText};
+use crate::AppState;
+use crate::display::camera::MainCamera;
+use crate::simulation::server_sim::player::You;
+use crate::simulation::server_sim::weapon::WeaponStatus;
+use crate::utils::ndc::world_to_screen;
+
+const HUD_WIDTH: f32 = 120.;
+const OVERHEATED_COLOR: Color32 = Color32::from_rgb(230, 60, 60);
+const HEAT_COLOR: Color32 = Color32::from_rgb(240, 150, 40);
+
+/// Ammo, reload and heat of your weapon, shown under your tank opposite the health display.
+pub struct AmmoUIPlugin;
+
+impl Plugin for AmmoUIPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_system(display_ammo.in_set(OnUpdate(AppState::InGame)))
+            .add_system(remove_weapon_status.in_schedule(OnExit(AppState::InGame)));
+    }
+}
+
+fn display_ammo(
+    mut contexts: EguiContexts,
+    status: Option<Res<WeaponStatus>>,
+    you_q: Query<&GlobalTransform, With<You>>,
+    camera_q: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    window_q: Query<&Window, With<PrimaryWindow>>,
+) {
+    let Some(status) = status else { return; };
+    let Ok(you_trans) = you_q.get_single() else { return; };
+    let Ok((cam, cam_trans)) = camera_q.get_single() else { return; };
+    let Ok(window) = window_q.get_single() else { return; };
+
+    let position = world_to_screen(you_trans.translation().truncate(), window.height(), cam, cam_trans)
+        + Vec2::new(-HUD_WIDTH / 2., 60.);
+
+    egui::Area::new("Ammo Area")
+        .fixed_pos(position.as_ref())
+        .interactable(false)
+        .show(contexts.ctx_mut(), |ui| {
+            egui::Frame::dark_canvas(&egui::Style::default()).show(ui, |ui| {
+                ui.set_width(HUD_WIDTH);
+                ui.label(&status.display_name);
+
+                match (status.reload_progress, status.ammo) {
+                    (Some(progress), _) => {
+                        ui.add(ProgressBar::new(progress).text("Reloading"));
+                    }
+                    (None, Some((ammo, size))) => {
+                        ui.label(format!("{ammo}/{size}"));
+                    }
+                    (None, None) => {}
+                }
+
+                if let Some(heat) = status.heat {
+                    let (color, text) = if status.overheated {
+                        (OVERHEATED_COLOR, "Overheated")
+                    } else {
+                        (HEAT_COLOR, "Heat")
+                    };
+                    ui.add(ProgressBar::new(heat).fill(color).text(RichText::new(text).color(Color32::WHITE)));
+                }
+            });
+        });
+}
+
+fn remove_weapon_status(mut commands: Commands) {
+    commands.remove_resource::<WeaponStatus>();
+}
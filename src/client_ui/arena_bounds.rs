@@ -0,0 +1,70 @@
+use bevy::app::App;
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use bevy_egui::{egui, EguiContexts};
+use bevy_egui::egui::{Align2, Color32, LayerId, Pos2, RichText, Shape, Stroke};
+use crate::AppState;
+use crate::display::camera::MainCamera;
+use crate::simulation::map::{Boundary, MapData};
+use crate::simulation::server_sim::player::You;
+use crate::utils::ndc::world_to_screen;
+use crate::utils::ui::DEFAULT_FRAME;
+
+pub struct ArenaBoundsUIPlugin;
+
+impl Plugin for ArenaBoundsUIPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            (
+                draw_arena_bounds,
+                out_of_bounds_warning,
+            ).in_set(OnUpdate(AppState::InGame))
+        );
+    }
+}
+
+pub fn boundary_color(boundary: Boundary) -> Color32 {
+    match boundary {
+        Boundary::Walls => Color32::from_rgb(120, 80, 40),
+        Boundary::DamageZone { .. } => Color32::RED,
+    }
+}
+
+fn draw_arena_bounds(
+    mut contexts: EguiContexts,
+    map: Option<Res<MapData>>,
+    camera_q: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    window_q: Query<&Window, With<PrimaryWindow>>,
+) {
+    let Some(map) = map else { return; };
+    let Ok((cam, cam_trans)) = camera_q.get_single() else { return; };
+    let Ok(window) = window_q.get_single() else { return; };
+
+    let points = map.bounds.iter().map(|&point| {
+        let screen = world_to_screen(point, window.height(), cam, cam_trans);
+        Pos2::new(screen.x, screen.y)
+    }).collect();
+
+    contexts.ctx_mut().layer_painter(LayerId::background())
+        .add(Shape::closed_line(points, Stroke::new(4., boundary_color(map.boundary))));
+}
+
+fn out_of_bounds_warning(
+    mut contexts: EguiContexts,
+    map: Option<Res<MapData>>,
+    you_q: Query<&Transform, With<You>>,
+) {
+    let Some(map) = map else { return; };
+    let Boundary::DamageZone { .. } = map.boundary else { return; };
+    let Ok(you_trans) = you_q.get_single() else { return; };
+    if map.contains(you_trans.translation.truncate()) { return; }
+
+    egui::Area::new("Out Of Bounds Area")
+        .anchor(Align2::CENTER_TOP, [0., 60.])
+        .interactable(false)
+        .show(contexts.ctx_mut(), |ui| {
+            DEFAULT_FRAME.show(ui, |ui| {
+                ui.label(RichText::new("Out of bounds! Return to the arena").heading().color(Color32::RED));
+            });
+        });
+}
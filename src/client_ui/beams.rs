@@ -0,0 +1,80 @@
+use bevy::app::App;
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use bevy_egui::EguiContexts;
+use bevy_egui::egui::{Color32, LayerId, Pos2, Stroke};
+use crate::AppState;
+use crate::client_networking::RecvBeamEvent;
+use crate::display::camera::MainCamera;
+use crate::utils::ndc::world_to_screen;
+
+const BEAM_LIFETIME: f32 = 0.2;
+const BEAM_WIDTH: f32 = 6.;
+const BEAM_COLOR: Color32 = Color32::from_rgb(120, 230, 255);
+
+/// Draws the shots of hitscan weapons as lines that quickly fade out.
+pub struct BeamsUIPlugin;
+
+impl Plugin for BeamsUIPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<Beams>()
+            .add_systems(
+                (
+                    add_beams.before(draw_beams),
+                    draw_beams,
+                ).in_set(OnUpdate(AppState::InGame))
+            )
+            .add_system(clear_beams.in_schedule(OnExit(AppState::InGame)));
+    }
+}
+
+#[derive(Resource, Default)]
+struct Beams {
+    beams: Vec<Beam>,
+}
+
+struct Beam {
+    start: Vec2,
+    end: Vec2,
+    age: f32,
+}
+
+fn add_beams(
+    mut events: EventReader<RecvBeamEvent>,
+    mut beams: ResMut<Beams>,
+) {
+    events.iter().for_each(|e| {
+        beams.beams.push(Beam { start: e.start, end: e.end, age: 0. });
+    });
+}
+
+fn draw_beams(
+    mut contexts: EguiContexts,
+    mut beams: ResMut<Beams>,
+    camera_q: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    window_q: Query<&Window, With<PrimaryWindow>>,
+    time: Res<Time>,
+) {
+    beams.beams.iter_mut().for_each(|beam| beam.age += time.delta_seconds());
+    beams.beams.retain(|beam| beam.age < BEAM_LIFETIME);
+    if beams.beams.is_empty() { return; }
+
+    let Ok((cam, cam_trans)) = camera_q.get_single() else { return; };
+    let Ok(window) = window_q.get_single() else { return; };
+
+    let painter = contexts.ctx_mut().layer_painter(LayerId::background());
+
+    beams.beams.iter().for_each(|beam| {
+        let fade = 1. - beam.age / BEAM_LIFETIME;
+        let start = world_to_screen(beam.start, window.height(), cam, cam_trans);
+        let end = world_to_screen(beam.end, window.height(), cam, cam_trans);
+
+        painter.line_segment([Pos2::new(start.x, start.y), Pos2::new(end.x, end.y)],
+                             Stroke::new(BEAM_WIDTH * fade, BEAM_COLOR.linear_multiply(fade)));
+    });
+}
+
+fn clear_beams(mut beams: ResMut<Beams>) {
+    beams.beams.clear();
+}
@@ -0,0 +1,88 @@
+use bevy::app::App;
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use bevy_egui::{egui, EguiContexts};
+use bevy_egui::egui::{Align2, Color32, LayerId, Pos2, ProgressBar, RichText, Stroke};
+use crate::AppState;
+use crate::client_ui::teams::team_color;
+use crate::display::camera::MainCamera;
+use crate::simulation::server_sim::game_mode::king_of_the_hill::{ControlPoint, ControlPoints};
+use crate::utils::ndc::world_to_screen;
+use crate::utils::ui::DEFAULT_FRAME;
+
+pub struct ControlPointsUIPlugin;
+
+impl Plugin for ControlPointsUIPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            (
+                draw_control_points,
+                control_points_ui,
+            ).in_set(OnUpdate(AppState::InGame))
+        );
+    }
+}
+
+pub const CONTESTED_COLOR: Color32 = Color32::from_rgb(230, 60, 60);
+
+pub fn control_point_color(point: &ControlPoint) -> Color32 {
+    point.owner.map_or(Color32::WHITE, team_color)
+}
+
+fn draw_control_points(
+    mut contexts: EguiContexts,
+    control_points: Option<Res<ControlPoints>>,
+    camera_q: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    window_q: Query<&Window, With<PrimaryWindow>>,
+) {
+    let Some(control_points) = control_points else { return; };
+    let Ok((cam, cam_trans)) = camera_q.get_single() else { return; };
+    let Ok(window) = window_q.get_single() else { return; };
+
+    let painter = contexts.ctx_mut().layer_painter(LayerId::background());
+
+    control_points.points.iter().for_each(|point| {
+        let center = world_to_screen(point.center, window.height(), cam, cam_trans);
+        let edge = world_to_screen(point.center + Vec2::X * point.radius, window.height(), cam, cam_trans);
+        let center_pos = Pos2::new(center.x, center.y);
+        let radius = center.distance(edge);
+
+        painter.circle(center_pos, radius, control_point_color(point).linear_multiply(0.15),
+                       Stroke::new(4., if point.contested { CONTESTED_COLOR } else { control_point_color(point) }));
+
+        if let Some(capturing) = point.capturing {
+            painter.circle_stroke(center_pos, radius * point.progress, Stroke::new(2., team_color(capturing)));
+        }
+
+        painter.text(center_pos, Align2::CENTER_CENTER, &point.name,
+                     egui::FontId::proportional(32.), control_point_color(point));
+    });
+}
+
+fn control_points_ui(
+    mut contexts: EguiContexts,
+    control_points: Option<Res<ControlPoints>>,
+) {
+    let Some(control_points) = control_points else { return; };
+
+    egui::Area::new("Control Points Area")
+        .anchor(Align2::RIGHT_TOP, [0., 0.])
+        .show(contexts.ctx_mut(), |ui| {
+            DEFAULT_FRAME.outer_margin(10.0).show(ui, |ui| {
+                ui.set_width(200.);
+                control_points.points.iter().for_each(|point| {
+                    let status = match (point.owner, point.contested) {
+                        (_, true) => "Contested".to_owned(),
+                        (Some(owner), false) => format!("{} team", owner.name()),
+                        (None, false) => "Neutral".to_owned(),
+                    };
+                    ui.label(RichText::new(format!("{}: {}", point.name, status))
+                        .color(if point.contested { CONTESTED_COLOR } else { control_point_color(point) }));
+
+                    let bar = ProgressBar::new(point.progress)
+                        .fill(point.capturing.map_or(Color32::GRAY, team_color));
+                    ui.add(bar);
+                });
+            });
+        });
+}
@@ -0,0 +1,81 @@
+use bevy::app::App;
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use bevy_egui::EguiContexts;
+use bevy_egui::egui::{Color32, LayerId, Pos2, Stroke};
+use crate::AppState;
+use crate::client_networking::RecvExplosionEvent;
+use crate::display::camera::MainCamera;
+use crate::utils::ndc::world_to_screen;
+
+const BLAST_LIFETIME: f32 = 0.5;
+const BLAST_COLOR: Color32 = Color32::from_rgb(255, 150, 40);
+
+/// Flashes a fading ring wherever the server says something exploded.
+pub struct ExplosionsUIPlugin;
+
+impl Plugin for ExplosionsUIPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<Blasts>()
+            .add_systems(
+                (
+                    add_blasts.before(draw_blasts),
+                    draw_blasts,
+                ).in_set(OnUpdate(AppState::InGame))
+            )
+            .add_system(clear_blasts.in_schedule(OnExit(AppState::InGame)));
+    }
+}
+
+#[derive(Resource, Default)]
+struct Blasts {
+    blasts: Vec<Blast>,
+}
+
+struct Blast {
+    position: Vec2,
+    radius: f32,
+    age: f32,
+}
+
+fn add_blasts(
+    mut events: EventReader<RecvExplosionEvent>,
+    mut blasts: ResMut<Blasts>,
+) {
+    events.iter().for_each(|e| {
+        blasts.blasts.push(Blast { position: e.position, radius: e.radius, age: 0. });
+    });
+}
+
+fn draw_blasts(
+    mut contexts: EguiContexts,
+    mut blasts: ResMut<Blasts>,
+    camera_q: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    window_q: Query<&Window, With<PrimaryWindow>>,
+    time: Res<Time>,
+) {
+    blasts.blasts.iter_mut().for_each(|blast| blast.age += time.delta_seconds());
+    blasts.blasts.retain(|blast| blast.age < BLAST_LIFETIME);
+    if blasts.blasts.is_empty() { return; }
+
+    let Ok((cam, cam_trans)) = camera_q.get_single() else { return; };
+    let Ok(window) = window_q.get_single() else { return; };
+
+    let painter = contexts.ctx_mut().layer_painter(LayerId::background());
+
+    blasts.blasts.iter().for_each(|blast| {
+        let progress = blast.age / BLAST_LIFETIME;
+        let center = world_to_screen(blast.position, window.height(), cam, cam_trans);
+        let edge = world_to_screen(blast.position + Vec2::X * blast.radius, window.height(), cam, cam_trans);
+        // Grows quickly to the full radius while fading out
+        let radius = center.distance(edge) * (1. - (1. - progress).powi(3));
+        let color = BLAST_COLOR.linear_multiply(1. - progress);
+
+        painter.circle(Pos2::new(center.x, center.y), radius, color.linear_multiply(0.3), Stroke::new(3., color));
+    });
+}
+
+fn clear_blasts(mut blasts: ResMut<Blasts>) {
+    blasts.blasts.clear();
+}
@@ -0,0 +1,93 @@
+use std::collections::VecDeque;
+use bevy::app::App;
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use bevy_egui::egui::{Align2, Color32, RichText};
+use crate::AppState;
+use crate::client_networking::RecvFlagEvent;
+use crate::client_ui::teams::team_color;
+use crate::simulation::server_sim::game_mode::capture_the_flag::FlagAction;
+use crate::utils::ui::DEFAULT_FRAME;
+
+const FEED_LENGTH: usize = 5;
+const FEED_ENTRY_LIFETIME: f32 = 6.;
+
+/// Announcements from the match, newest first, that fade out after a few seconds.
+pub struct HudFeedUIPlugin;
+
+impl Plugin for HudFeedUIPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<HudFeed>()
+            .add_systems(
+                (
+                    announce_flag_events.before(hud_feed_ui),
+                    hud_feed_ui,
+                ).in_set(OnUpdate(AppState::InGame))
+            )
+            .add_system(clear_hud_feed.in_schedule(OnExit(AppState::InGame)));
+    }
+}
+
+#[derive(Resource, Default)]
+pub struct HudFeed {
+    entries: VecDeque<FeedEntry>,
+}
+
+struct FeedEntry {
+    text: String,
+    color: Color32,
+    age: f32,
+}
+
+impl HudFeed {
+    pub fn push(&mut self, text: String, color: Color32) {
+        self.entries.push_front(FeedEntry { text, color, age: 0. });
+        self.entries.truncate(FEED_LENGTH);
+    }
+}
+
+fn announce_flag_events(
+    mut events: EventReader<RecvFlagEvent>,
+    mut feed: ResMut<HudFeed>,
+) {
+    events.iter().for_each(|e| {
+        let flag = format!("the {} flag", e.event.team.name());
+        let who = e.event.player_id.map_or("Nobody".to_owned(), |id| format!("Player {}", id));
+        let text = match e.event.action {
+            FlagAction::Taken => format!("{} took {}", who, flag),
+            FlagAction::Dropped => format!("{} dropped {}", who, flag),
+            FlagAction::Returned if e.event.player_id.is_none() => format!("The {} flag returned", e.event.team.name()),
+            FlagAction::Returned => format!("{} returned {}", who, flag),
+            FlagAction::Captured => format!("{} captured {}!", who, flag),
+        };
+        feed.push(text, team_color(e.event.team));
+    });
+}
+
+fn hud_feed_ui(
+    mut contexts: EguiContexts,
+    mut feed: ResMut<HudFeed>,
+    time: Res<Time>,
+) {
+    feed.entries.iter_mut().for_each(|entry| entry.age += time.delta_seconds());
+    feed.entries.retain(|entry| entry.age < FEED_ENTRY_LIFETIME);
+    if feed.entries.is_empty() { return; }
+
+    egui::Area::new("Hud Feed Area")
+        .anchor(Align2::CENTER_TOP, [0., 70.])
+        .show(contexts.ctx_mut(), |ui| {
+            DEFAULT_FRAME.outer_margin(10.0).show(ui, |ui| {
+                ui.vertical_centered(|ui| {
+                    feed.entries.iter().for_each(|entry| {
+                        let alpha = 1. - entry.age / FEED_ENTRY_LIFETIME;
+                        ui.label(RichText::new(&entry.text).color(entry.color.linear_multiply(alpha)));
+                    });
+                });
+            });
+        });
+}
+
+fn clear_hud_feed(mut feed: ResMut<HudFeed>) {
+    feed.entries.clear();
+}
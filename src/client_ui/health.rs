@@ -4,7 +4,8 @@ use bevy::window::PrimaryWindow;
 use bevy_egui::{egui, EguiContexts};
 use crate::AppState;
 use crate::display::camera::MainCamera;
-use crate::simulation::server_sim::player::Health;
+use crate::simulation::Lobby;
+use crate::simulation::server_sim::player::{Health, Player};
 use crate::utils::ndc::world_to_screen;
 
 pub struct HealthUiPlugin;
@@ -22,15 +23,16 @@ struct UIWidth(pub f32);
 
 fn display_health(
     mut contexts: EguiContexts,
-    healths: Query<(Entity, &GlobalTransform, &Health)>,
+    healths: Query<(Entity, &GlobalTransform, &Health, Option<&Player>)>,
     camera_q: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
     window_q: Query<&Window, With<PrimaryWindow>>,
     mut ui_width: ResMut<UIWidth>,
+    lobby: Res<Lobby>,
 ) {
     let Ok((cam, cam_trans)) = camera_q.get_single() else { return; };
     let Ok(window) = window_q.get_single() else { return; };
 
-    healths.iter().for_each(|(ent, trans, health)| {
+    healths.iter().for_each(|(ent, trans, health, player)| {
         let area = egui::Area::new(format!("{} Health Area", ent.index()))
             .fixed_pos(
                 (world_to_screen(
@@ -42,7 +44,12 @@ fn display_health(
             .show(contexts.ctx_mut(), |ui| {
                 egui::Frame::dark_canvas(&egui::Style::default())
                     .show(ui, |ui| {
-                        ui.label(format!("{}/{}", health.health, health.max_health));
+                        let level = player.and_then(|player| lobby.player_data.get(&player.id))
+                            .map(|data| data.progression.level);
+                        match level {
+                            Some(level) => ui.label(format!("Lv {} | {:.0}/{:.0}", level, health.health, health.max_health)),
+                            None => ui.label(format!("{:.0}/{:.0}", health.health, health.max_health)),
+                        };
                     });
             });
 
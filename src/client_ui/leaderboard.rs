@@ -1,10 +1,11 @@
 use bevy::prelude::*;
 use bevy_egui::{egui, EguiContexts};
-use bevy_egui::egui::Align2;
+use bevy_egui::egui::{Align2, Color32, RichText};
 use crate::AppState;
+use crate::client_ui::teams::team_color;
 use crate::simulation::Lobby;
-use crate::utils::networking::messages::PlayerId;
-use crate::simulation::PlayerData;
+use crate::simulation::server_sim::game_mode::{ActiveGameMode, rank_players, score_by_kills};
+use crate::simulation::server_sim::game_mode::teams::rank_teams;
 
 pub struct LeaderboardUIPlugin;
 
@@ -17,6 +18,7 @@ impl Plugin for LeaderboardUIPlugin {
 fn leaderboard_ui(
     mut contexts: EguiContexts,
     lobby: Res<Lobby>,
+    mode: Option<Res<ActiveGameMode>>,
 ) {
     egui::Area::new("Leaderboard_Area")
         .anchor(Align2::LEFT_TOP, [0., 0.])
@@ -30,18 +32,28 @@ fn leaderboard_ui(
                         ui.label("Leaderboard");
                     });
                     ui.separator();
-                    egui::Frame::group(&egui::Style::default()).show(ui, |ui| {
-                        let mut player_vec = lobby.player_data.clone().into_iter()
-                            .collect::<Vec<(PlayerId, PlayerData)>>();
-
-                        player_vec.sort_by(|(_, data_a), (_, data_b)| {
-                            data_b.kills.cmp(&data_a.kills)
+                    if let Some(mode) = &mode && !mode.teams.is_empty() {
+                        egui::Frame::group(&egui::Style::default()).show(ui, |ui| {
+                            rank_teams(&lobby, &mode.teams, mode.rules.score).iter().for_each(|(team, score)| {
+                                ui.label(RichText::new(format!("{} team: {}", team.name(), score))
+                                    .color(team_color(*team)));
+                            });
                         });
+                    }
+                    egui::Frame::group(&egui::Style::default()).show(ui, |ui| {
+                        let player_vec = match &mode {
+                            Some(mode) => rank_players(&lobby, mode.rules.score),
+                            None => rank_players(&lobby, score_by_kills),
+                        };
 
                         player_vec.iter().for_each(|(id, data)| {
-                            ui.label(format!("Player {}: {} kill{}",
-                                             id, data.kills, if data.kills != 1 { "s" } else { "" }
-                            ));
+                            let color = data.team.map_or(Color32::GRAY, team_color);
+                            let mut text = format!("Player {} (Lv {}): {} kill{}",
+                                                   id, data.progression.level, data.kills, if data.kills != 1 { "s" } else { "" });
+                            if let Some(mode) = &mode && let Some(score_name) = mode.rules.extra_score {
+                                text += &format!(", {} {}", (mode.rules.score)(data), score_name);
+                            }
+                            ui.label(RichText::new(text).color(color));
                         });
                     });
                 });
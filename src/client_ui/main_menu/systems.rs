@@ -28,6 +28,7 @@ pub fn main_menu_gui(
     mut center_menu_state: ResMut<CenterMenuState>,
     connect_writer: EventWriter<OnConnectAttempt>,
     host_writer: EventWriter<OnHostAttempt>,
+    mut next_state: ResMut<NextState<AppState>>,
 ) {
     egui::Area::new("Main Menu Center Area")
         .anchor(Align2::CENTER_CENTER, [0., 0.])
@@ -36,7 +37,7 @@ pub fn main_menu_gui(
                 ui.set_width(CENTER_WIDTH);
                 match *center_menu_state {
                     CenterMenuState::Main => {
-                        ui.center_menu(&mut center_menu_state);
+                        ui.center_menu(&mut center_menu_state, &mut next_state);
                     }
                     CenterMenuState::Join => {
                         ui.connect_menu(
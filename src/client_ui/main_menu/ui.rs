@@ -1,6 +1,7 @@
 use crate::client_ui::main_menu::{CenterMenuState, OnConnectAttempt, OnHostAttempt};
 use crate::server_networking::DEFAULT_SERVER_PORT;
-use bevy::prelude::EventWriter;
+use crate::AppState;
+use bevy::prelude::{EventWriter, NextState};
 use bevy::utils::default;
 use bevy_egui::egui;
 use bevy_egui::egui::epaint::Shadow;
@@ -23,7 +24,7 @@ pub static MAIN_MENU_FRAME: Lazy<Frame> = Lazy::new(|| Frame {
 });
 
 pub trait MainMenuExt {
-    fn center_menu(&mut self, center_menu_state: &mut CenterMenuState);
+    fn center_menu(&mut self, center_menu_state: &mut CenterMenuState, next_state: &mut NextState<AppState>);
 
     fn connect_menu(
         &mut self,
@@ -41,7 +42,7 @@ pub trait MainMenuExt {
 }
 
 impl MainMenuExt for Ui {
-    fn center_menu(&mut self, center_menu_state: &mut CenterMenuState) {
+    fn center_menu(&mut self, center_menu_state: &mut CenterMenuState, next_state: &mut NextState<AppState>) {
         self.vertical_centered(|ui| {
             if ui.button(RichText::new("Join Server").heading()).clicked() {
                 *center_menu_state = CenterMenuState::Join;
@@ -54,6 +55,12 @@ impl MainMenuExt for Ui {
             if ui.button(RichText::new("Host Server").heading()).clicked() {
                 *center_menu_state = CenterMenuState::Host;
             }
+
+            ui.add_space(20.);
+
+            if ui.button("Map Editor").clicked() {
+                next_state.set(AppState::MapEditor);
+            }
         });
     }
 
@@ -0,0 +1,112 @@
+use bevy::app::App;
+use bevy::math::Vec2;
+use bevy::prelude::*;
+use crate::AppState;
+use crate::simulation::map::{MapData, Obstacle};
+use crate::simulation::server_sim::pickup::PickupKind;
+use crate::simulation::server_sim::despawn_all_entities;
+
+mod systems;
+mod ui;
+
+pub struct MapEditorPlugin;
+
+impl Plugin for MapEditorPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .insert_resource(EditorState::default())
+            .add_system(systems::init.in_schedule(OnEnter(AppState::MapEditor)))
+            .add_systems(
+                (
+                    systems::editor_panel,
+                    systems::editor_camera.after(systems::editor_panel),
+                    systems::editor_mouse.after(systems::editor_panel),
+                    systems::rebuild_preview.after(systems::editor_mouse),
+                    systems::sync_spawn_markers.after(systems::rebuild_preview),
+                    systems::draw_overlay.after(systems::editor_mouse),
+                ).in_set(OnUpdate(AppState::MapEditor))
+            )
+            .add_system(despawn_all_entities.in_schedule(OnExit(AppState::MapEditor)));
+    }
+}
+
+#[derive(Resource)]
+pub struct EditorMap {
+    pub map: MapData,
+    pub rebuild: bool,
+}
+
+impl EditorMap {
+    pub fn new(map: MapData) -> Self {
+        EditorMap {
+            map,
+            rebuild: true,
+        }
+    }
+}
+
+#[derive(Default, Clone, Copy, PartialEq, Eq)]
+pub enum EditorTool {
+    #[default]
+    SpawnPoints,
+    Obstacles,
+    Trees,
+    Pickups,
+    Bounds,
+}
+
+#[derive(Resource)]
+pub struct EditorState {
+    pub tool: EditorTool,
+    pub tree_spacing: f32,
+    /// What the pickup tool places a spawner for.
+    pub pickup: PickupKind,
+    pub respawn_time: f32,
+    pub drawing: Vec<Vec2>,
+    pub dragging: Option<usize>,
+    pub file_path: String,
+    pub seed: String,
+    pub status: String,
+    /// Counted in `rebuild_preview` rather than on every frame, the flood fill isn't cheap.
+    pub tree_count: usize,
+    pub unreachable_spawns: usize,
+}
+
+impl Default for EditorState {
+    fn default() -> Self {
+        EditorState {
+            tool: EditorTool::default(),
+            tree_spacing: 200.,
+            pickup: PickupKind::SPAWNABLE[0].clone(),
+            respawn_time: 20.,
+            drawing: Vec::new(),
+            dragging: None,
+            file_path: "maps/custom.ron".into(),
+            seed: "".into(),
+            status: "".into(),
+            tree_count: 0,
+            unreachable_spawns: 0,
+        }
+    }
+}
+
+impl EditorState {
+    /// Turns the polygon being drawn into an obstacle or the new map bounds, depending on the tool.
+    pub fn finish_drawing(&mut self, editor_map: &mut EditorMap) {
+        if self.drawing.len() < 3 { return; }
+
+        let polygon = std::mem::take(&mut self.drawing);
+        match self.tool {
+            EditorTool::Obstacles => editor_map.map.obstacles.push(Obstacle {
+                polygon,
+                tree_spacing: self.tree_spacing,
+            }),
+            EditorTool::Bounds => editor_map.map.bounds = polygon,
+            _ => return,
+        }
+        editor_map.rebuild = true;
+    }
+}
+
+#[derive(Component)]
+pub struct SpawnMarker(pub usize);
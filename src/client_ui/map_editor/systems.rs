@@ -0,0 +1,288 @@
+use bevy::input::mouse::MouseWheel;
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use bevy_egui::{egui, EguiContexts};
+use bevy_egui::egui::{Align2, Color32, FontId, LayerId, Pos2, Shape, Stroke};
+use crate::AppState;
+use crate::asset_loader::components::SpriteEnum;
+use crate::client_ui::arena_bounds::boundary_color;
+use crate::client_ui::map_editor::{EditorMap, EditorState, EditorTool, SpawnMarker};
+use crate::client_ui::map_editor::ui::EditorPanelExt;
+use crate::display::camera::MainCamera;
+use crate::display::sprite_updater::{AutoSorted, PLAYER_LAYER};
+use crate::simulation::map::{default_map, MAP_CLEARANCE, MapEntity, spawn_map};
+use crate::simulation::server_sim::pickup::{PICKUP_RADIUS, PickupSpawner};
+use crate::utils::commands::despawn::CustomDespawnExt;
+use crate::utils::is_point_in_polygon;
+use crate::utils::ndc::{screen_to_world, world_to_screen, ScreenSize};
+use crate::utils::prefabs::{default_camera, TREE_COLLIDER_RADIUS};
+
+const PICK_RADIUS: f32 = 150.;
+const CAMERA_PAN_SPEED: f32 = 800.;
+
+pub fn init(mut commands: Commands, editor_map: Option<ResMut<EditorMap>>) {
+    commands.spawn(default_camera());
+
+    match editor_map {
+        Some(mut editor_map) => editor_map.rebuild = true,
+        None => commands.insert_resource(EditorMap::new(default_map())),
+    }
+}
+
+pub fn editor_panel(
+    mut contexts: EguiContexts,
+    mut state: ResMut<EditorState>,
+    mut editor_map: ResMut<EditorMap>,
+    mut next_state: ResMut<NextState<AppState>>,
+) {
+    egui::SidePanel::left("Map Editor Panel")
+        .resizable(false)
+        .show(contexts.ctx_mut(), |ui| {
+            ui.heading("Map Editor");
+            ui.separator();
+            ui.tool_section(&mut state, &mut editor_map);
+            ui.separator();
+            ui.obstacle_section(&state, &mut editor_map);
+            ui.separator();
+            ui.file_section(&mut state, &mut editor_map);
+            ui.separator();
+            if ui.button("Back to Main Menu").clicked() {
+                next_state.set(AppState::MainMenu);
+            }
+        });
+}
+
+pub fn editor_camera(
+    mut contexts: EguiContexts,
+    keys: Res<Input<KeyCode>>,
+    mut scroll_events: EventReader<MouseWheel>,
+    mut camera_q: Query<(&mut Transform, &mut OrthographicProjection), With<MainCamera>>,
+    time: Res<Time>,
+) {
+    let Ok((mut trans, mut projection)) = camera_q.get_single_mut() else { return; };
+
+    let ctx = contexts.ctx_mut();
+    if !ctx.wants_keyboard_input() {
+        let direction = [
+            (KeyCode::W, Vec2::Y),
+            (KeyCode::A, Vec2::NEG_X),
+            (KeyCode::S, Vec2::NEG_Y),
+            (KeyCode::D, Vec2::X),
+        ].into_iter()
+            .filter(|(key, _)| keys.pressed(*key))
+            .map(|(_, dir)| dir)
+            .sum::<Vec2>();
+
+        trans.translation +=
+            (direction * CAMERA_PAN_SPEED * projection.scale * time.delta_seconds()).extend(0.);
+    }
+
+    if !ctx.is_pointer_over_area() {
+        scroll_events.iter().for_each(|e| {
+            projection.scale = (projection.scale * (1. - e.y * 0.1)).clamp(0.5, 20.);
+        });
+    }
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn editor_mouse(
+    mut contexts: EguiContexts,
+    buttons: Res<Input<MouseButton>>,
+    keys: Res<Input<KeyCode>>,
+    window_q: Query<&Window, With<PrimaryWindow>>,
+    camera_q: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    mut state: ResMut<EditorState>,
+    mut editor_map: ResMut<EditorMap>,
+) {
+    // The preview and counts only catch up with a dragged spawn point once it's dropped
+    if buttons.just_released(MouseButton::Left) && state.dragging.take().is_some() {
+        editor_map.rebuild = true;
+    }
+
+    let ctx = contexts.ctx_mut();
+    if !ctx.wants_keyboard_input() {
+        if keys.just_pressed(KeyCode::Return) {
+            state.finish_drawing(&mut editor_map);
+        }
+        if keys.just_pressed(KeyCode::Escape) {
+            state.drawing.clear();
+        }
+    }
+
+    if ctx.is_pointer_over_area() || ctx.wants_pointer_input() { return; }
+
+    let Ok((camera, camera_transform)) = camera_q.get_single() else { return; };
+    let Ok(window) = window_q.get_single() else { return; };
+    let Some(screen_pos) = window.cursor_position() else { return; };
+    let cursor = screen_to_world(screen_pos, window.screen_size(), camera, camera_transform);
+
+    let left = buttons.just_pressed(MouseButton::Left);
+    let right = buttons.just_pressed(MouseButton::Right);
+    let state = &mut *state;
+    let editor_map = &mut *editor_map;
+    let map = &mut editor_map.map;
+
+    match state.tool {
+        EditorTool::SpawnPoints => {
+            if let Some(index) = state.dragging {
+                map.spawn_points[index] = cursor;
+            } else if left {
+                match nearest_point(&map.spawn_points, cursor, PICK_RADIUS) {
+                    Some(index) => state.dragging = Some(index),
+                    None => {
+                        map.spawn_points.push(cursor);
+                        editor_map.rebuild = true;
+                    }
+                }
+            } else if right && let Some(index) = nearest_point(&map.spawn_points, cursor, PICK_RADIUS) {
+                map.spawn_points.remove(index);
+                editor_map.rebuild = true;
+            }
+        }
+        EditorTool::Trees => {
+            if left {
+                map.trees.push(cursor);
+                editor_map.rebuild = true;
+            } else if right && let Some(index) = nearest_point(&map.trees, cursor, TREE_COLLIDER_RADIUS) {
+                map.trees.remove(index);
+                editor_map.rebuild = true;
+            }
+        }
+        EditorTool::Pickups => {
+            let positions = map.pickup_spawners.iter().map(|spawner| spawner.position).collect::<Vec<_>>();
+            if left {
+                map.pickup_spawners.push(PickupSpawner {
+                    position: cursor,
+                    kind: state.pickup.clone(),
+                    respawn_time: state.respawn_time,
+                });
+                editor_map.rebuild = true;
+            } else if right && let Some(index) = nearest_point(&positions, cursor, PICKUP_RADIUS) {
+                map.pickup_spawners.remove(index);
+                editor_map.rebuild = true;
+            }
+        }
+        EditorTool::Obstacles => {
+            if left {
+                state.drawing.push(cursor);
+            } else if right && let Some(index) = map.obstacles.iter()
+                .position(|obstacle| is_point_in_polygon(&cursor, &obstacle.polygon)) {
+                map.obstacles.remove(index);
+                editor_map.rebuild = true;
+            }
+        }
+        EditorTool::Bounds => {
+            if left {
+                state.drawing.push(cursor);
+            } else if right {
+                state.drawing.pop();
+            }
+        }
+    }
+}
+
+fn nearest_point(points: &[Vec2], target: Vec2, max_distance: f32) -> Option<usize> {
+    points.iter().enumerate()
+        .map(|(index, point)| (index, point.distance(target)))
+        .filter(|(_, distance)| *distance <= max_distance)
+        .min_by(|(_, a), (_, b)| a.total_cmp(b))
+        .map(|(index, _)| index)
+}
+
+pub fn rebuild_preview(
+    mut editor_map: ResMut<EditorMap>,
+    mut state: ResMut<EditorState>,
+    map_entities: Query<Entity, With<MapEntity>>,
+    mut commands: Commands,
+) {
+    if !editor_map.rebuild { return; }
+    editor_map.rebuild = false;
+
+    map_entities.iter().for_each(|entity| commands.entity(entity).custom_despawn());
+
+    spawn_map(&mut commands, &editor_map.map);
+    state.tree_count = editor_map.map.tree_positions().len();
+    state.unreachable_spawns = editor_map.map.unreachable_spawn_points(MAP_CLEARANCE).len();
+    editor_map.map.spawn_points.iter().enumerate().for_each(|(index, &point)| {
+        commands.spawn(spawn_marker(index, point));
+    });
+}
+
+fn spawn_marker(index: usize, position: Vec2) -> impl Bundle {
+    (
+        Name::new("Spawn Marker"),
+        SpawnMarker(index),
+        MapEntity,
+        AutoSorted,
+        SpriteEnum::TankDefault,
+        Sprite {
+            color: Color::rgba(1., 1., 1., 0.5),
+            ..default()
+        },
+        TransformBundle::from_transform(Transform::from_translation(position.extend(PLAYER_LAYER))),
+    )
+}
+
+pub fn sync_spawn_markers(
+    editor_map: Res<EditorMap>,
+    mut markers: Query<(&SpawnMarker, &mut Transform)>,
+) {
+    if !editor_map.is_changed() { return; }
+
+    markers.iter_mut().for_each(|(marker, mut trans)| {
+        if let Some(point) = editor_map.map.spawn_points.get(marker.0) {
+            trans.translation = point.extend(trans.translation.z);
+        }
+    });
+}
+
+pub fn draw_overlay(
+    mut contexts: EguiContexts,
+    editor_map: Res<EditorMap>,
+    state: Res<EditorState>,
+    window_q: Query<&Window, With<PrimaryWindow>>,
+    camera_q: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+) {
+    let Ok((camera, camera_transform)) = camera_q.get_single() else { return; };
+    let Ok(window) = window_q.get_single() else { return; };
+
+    let to_screen = |point: &Vec2| {
+        let screen = world_to_screen(*point, window.height(), camera, camera_transform);
+        Pos2::new(screen.x, screen.y)
+    };
+
+    let painter = contexts.ctx_mut().layer_painter(LayerId::background());
+    let map = &editor_map.map;
+
+    painter.add(Shape::closed_line(
+        map.bounds.iter().map(to_screen).collect(),
+        Stroke::new(2., boundary_color(map.boundary)),
+    ));
+
+    map.obstacles.iter().for_each(|obstacle| {
+        painter.add(Shape::closed_line(
+            obstacle.polygon.iter().map(to_screen).collect(),
+            Stroke::new(2., Color32::GREEN),
+        ));
+    });
+
+    map.spawn_points.iter().enumerate().for_each(|(index, point)| {
+        painter.text(to_screen(point), Align2::CENTER_CENTER, format!("{}", index + 1),
+                     FontId::proportional(20.), Color32::WHITE);
+    });
+
+    map.pickup_spawners.iter().for_each(|spawner| {
+        let pos = to_screen(&spawner.position);
+        painter.circle_stroke(pos, 12., Stroke::new(2., Color32::LIGHT_BLUE));
+        painter.text(pos, Align2::CENTER_TOP, format!("\n{}", spawner.kind.name()),
+                     FontId::proportional(14.), Color32::LIGHT_BLUE);
+    });
+
+    painter.add(Shape::line(
+        state.drawing.iter().map(to_screen).collect(),
+        Stroke::new(2., Color32::YELLOW),
+    ));
+    state.drawing.iter().for_each(|point| {
+        painter.circle_filled(to_screen(point), 4., Color32::YELLOW);
+    });
+}
@@ -0,0 +1,174 @@
+use bevy_egui::egui::{DragValue, Slider, Ui};
+use crate::client_ui::map_editor::{EditorMap, EditorState, EditorTool};
+use crate::simulation::map::{Boundary, default_map, MapData};
+use crate::simulation::map::generator::{generate_map, MapGenConfig};
+use crate::simulation::server_sim::pickup::PickupKind;
+use crate::utils::generate_evenly_spaced_points_on_polygon_edges;
+
+pub trait EditorPanelExt {
+    fn tool_section(&mut self, state: &mut EditorState, editor_map: &mut EditorMap);
+
+    fn obstacle_section(&mut self, state: &EditorState, editor_map: &mut EditorMap);
+
+    fn file_section(&mut self, state: &mut EditorState, editor_map: &mut EditorMap);
+}
+
+impl EditorPanelExt for Ui {
+    fn tool_section(&mut self, state: &mut EditorState, editor_map: &mut EditorMap) {
+        self.label("Tool");
+        self.horizontal_wrapped(|ui| {
+            let tool = state.tool;
+            ui.selectable_value(&mut state.tool, EditorTool::SpawnPoints, "Spawn Points");
+            ui.selectable_value(&mut state.tool, EditorTool::Obstacles, "Obstacles");
+            ui.selectable_value(&mut state.tool, EditorTool::Trees, "Trees");
+            ui.selectable_value(&mut state.tool, EditorTool::Pickups, "Pickups");
+            ui.selectable_value(&mut state.tool, EditorTool::Bounds, "Bounds");
+            if tool != state.tool {
+                state.drawing.clear();
+            }
+        });
+
+        self.small(match state.tool {
+            EditorTool::SpawnPoints => "Left click to place or drag, right click to remove.",
+            EditorTool::Obstacles => "Left click to add corners, Enter to finish, right click an obstacle to remove it.",
+            EditorTool::Trees => "Left click to plant a tree, right click to remove one.",
+            EditorTool::Pickups => "Left click to place a pickup spawner, right click to remove one.",
+            EditorTool::Bounds => "Left click to add corners, right click to undo, Enter to finish.",
+        });
+
+        self.add(Slider::new(&mut state.tree_spacing, 100.0..=600.0).text("Tree spacing"));
+
+        if state.tool == EditorTool::Pickups {
+            self.horizontal_wrapped(|ui| {
+                PickupKind::SPAWNABLE.into_iter().for_each(|kind| {
+                    let name = kind.name().to_owned();
+                    ui.selectable_value(&mut state.pickup, kind, name);
+                });
+            });
+            self.add(Slider::new(&mut state.respawn_time, 5.0..=120.0).text("Respawn time"));
+        }
+
+        if matches!(state.tool, EditorTool::Obstacles | EditorTool::Bounds) {
+            self.horizontal(|ui| {
+                if ui.add_enabled(state.drawing.len() >= 3, bevy_egui::egui::Button::new("Finish")).clicked() {
+                    state.finish_drawing(editor_map);
+                }
+                if ui.button("Cancel").clicked() {
+                    state.drawing.clear();
+                }
+            });
+        }
+
+        if state.tool == EditorTool::Bounds {
+            let boundary = &mut editor_map.map.boundary;
+            let before = *boundary;
+            self.horizontal(|ui| {
+                ui.radio_value(boundary, Boundary::Walls, "Walls");
+                if ui.radio(matches!(boundary, Boundary::DamageZone { .. }), "Damage zone").clicked()
+                    && !matches!(boundary, Boundary::DamageZone { .. }) {
+                    *boundary = Boundary::DamageZone { damage_per_second: 10. };
+                }
+            });
+            if let Boundary::DamageZone { damage_per_second } = boundary {
+                self.add(DragValue::new(damage_per_second)
+                    .clamp_range(1.0..=100.0)
+                    .suffix(" damage/s"));
+            }
+            if *boundary != before {
+                editor_map.rebuild = true;
+            }
+        }
+
+        if state.tool == EditorTool::Bounds && self.button("Plant trees along bounds").clicked() {
+            let bounds = editor_map.map.bounds.as_slice();
+            let ring = generate_evenly_spaced_points_on_polygon_edges(bounds, state.tree_spacing);
+            editor_map.map.trees.extend(ring);
+            editor_map.rebuild = true;
+        }
+    }
+
+    fn obstacle_section(&mut self, state: &EditorState, editor_map: &mut EditorMap) {
+        let map = &editor_map.map;
+        self.label(format!("{} spawn points, {} trees, {} obstacles",
+                           map.spawn_points.len(),
+                           state.tree_count,
+                           map.obstacles.len()));
+
+        if state.unreachable_spawns > 0 {
+            self.colored_label(bevy_egui::egui::Color32::RED,
+                               format!("{} spawn point(s) can't be reached", state.unreachable_spawns));
+        }
+
+        let mut removed = None;
+        self.collapsing("Obstacles", |ui| {
+            editor_map.map.obstacles.iter_mut().enumerate().for_each(|(index, obstacle)| {
+                ui.horizontal(|ui| {
+                    ui.label(format!("Obstacle {}", index + 1));
+                    if ui.add(DragValue::new(&mut obstacle.tree_spacing)
+                        .clamp_range(100.0..=600.0)
+                        .prefix("spacing: ")).changed() {
+                        editor_map.rebuild = true;
+                    }
+                    if ui.small_button("Remove").clicked() {
+                        removed = Some(index);
+                    }
+                });
+            });
+        });
+
+        if let Some(index) = removed {
+            editor_map.map.obstacles.remove(index);
+            editor_map.rebuild = true;
+        }
+    }
+
+    fn file_section(&mut self, state: &mut EditorState, editor_map: &mut EditorMap) {
+        self.horizontal(|ui| {
+            ui.label("Seed:");
+            ui.text_edit_singleline(&mut state.seed);
+        });
+        self.horizontal(|ui| {
+            let seed = state.seed.parse::<u64>();
+            if ui.add_enabled(seed.is_ok(), bevy_egui::egui::Button::new("Generate")).clicked() {
+                *editor_map = EditorMap::new(generate_map(seed.unwrap(), &MapGenConfig::default()));
+            }
+            if ui.button("Default Map").clicked() {
+                *editor_map = EditorMap::new(default_map());
+            }
+            if ui.button("Clear").clicked() {
+                *editor_map = EditorMap::new(MapData {
+                    bounds: editor_map.map.bounds.clone(),
+                    boundary: editor_map.map.boundary,
+                    ..MapData::default()
+                });
+            }
+        });
+
+        self.separator();
+
+        self.horizontal(|ui| {
+            ui.label("File:");
+            ui.text_edit_singleline(&mut state.file_path);
+        });
+        self.horizontal(|ui| {
+            if ui.button("Save").clicked() {
+                state.status = match editor_map.map.save(&state.file_path) {
+                    Ok(()) => format!("Saved to {}", state.file_path),
+                    Err(e) => format!("Could not save: {}", e),
+                };
+            }
+            if ui.button("Load").clicked() {
+                state.status = match MapData::load(&state.file_path) {
+                    Ok(map) => {
+                        *editor_map = EditorMap::new(map);
+                        format!("Loaded {}", state.file_path)
+                    }
+                    Err(e) => format!("Could not load: {}", e),
+                };
+            }
+        });
+        if !state.status.is_empty() {
+            self.small(state.status.as_str());
+        }
+    }
+}
@@ -3,9 +3,10 @@ use bevy::prelude::*;
 use bevy_egui::{egui, EguiContexts};
 use bevy_egui::egui::*;
 use crate::AppState;
-use crate::simulation::{Lobby, PlayerData};
-use crate::simulation::server_sim::match_ffa::is_match_finished;
-use crate::utils::networking::messages::PlayerId;
+use crate::client_ui::teams::team_color;
+use crate::simulation::Lobby;
+use crate::simulation::server_sim::game_mode::{ActiveGameMode, is_match_over, MatchOutcome, MatchWinner, rank_players, score_by_kills};
+use crate::simulation::server_sim::game_mode::teams::rank_teams;
 
 pub struct MatchEndScreenUIPlugin;
 
@@ -14,7 +15,7 @@ impl Plugin for MatchEndScreenUIPlugin {
         app
             .add_system(
                 match_end_screen
-                    .run_if(is_match_finished)
+                    .run_if(is_match_over)
                     .in_set(OnUpdate(AppState::InGame))
             );
     }
@@ -23,6 +24,8 @@ impl Plugin for MatchEndScreenUIPlugin {
 fn match_end_screen(
     mut contexts: EguiContexts,
     lobby: Res<Lobby>,
+    outcome: Res<MatchOutcome>,
+    mode: Option<Res<ActiveGameMode>>,
 ) {
     egui::Area::new("Final Leaderboard Area")
         .anchor(Align2::CENTER_CENTER, [0., 0.])
@@ -31,6 +34,24 @@ fn match_end_screen(
             egui::Frame::menu(&egui::Style::default())
                 .outer_margin(10.0)
                 .show(ui, |ui| {
+                    ui.vertical_centered(|ui| {
+                        ui.heading(match outcome.winner {
+                            MatchWinner::Player(winner) => format!("Player {} wins!", winner),
+                            MatchWinner::Team(team) => format!("{} team wins!", team.name()),
+                            MatchWinner::Nobody => "Nobody wins".to_owned(),
+                        });
+                    });
+                    if let Some(mode) = &mode && !mode.teams.is_empty() {
+                        ui.separator();
+                        ui.horizontal_wrapped(|ui| {
+                            rank_teams(&lobby, &mode.teams, mode.rules.score).iter().for_each(|(team, score)| {
+                                ui.label(RichText::new(format!("{} team: {}", team.name(), score))
+                                    .color(team_color(*team))
+                                    .heading());
+                            });
+                        });
+                    }
+                    ui.separator();
                     ui.vertical_centered(|ui| {
                         ui.set_height(5.);
                         ui.label("Leaderboard");
@@ -39,30 +60,47 @@ fn match_end_screen(
                     egui::Frame::default()
                         .outer_margin(5.0)
                         .show(ui, |ui| {
-                            ui.columns(6, |columns| {
+                            ui.columns(8, |columns| {
                                 columns[0].label("Place");
                                 columns[1].label("Name");
+                                columns[2].label("Team");
+                                if let Some(mode) = &mode && let Some(score_name) = mode.rules.extra_score {
+                                    columns[3].label(capitalize(score_name));
+                                }
                                 columns[4].label("Kills");
-                                columns[5].label("Deaths");
+                                columns[5].label("Assists");
+                                columns[6].label("Deaths");
+                                columns[7].label("Damage");
                             });
                         });
                     egui::Frame::group(&egui::Style::default()).show(ui, |ui| {
-                        ui.columns(6, |columns| {
-                            let mut player_vec = lobby.player_data.clone().into_iter()
-                                .collect::<Vec<(PlayerId, PlayerData)>>();
-
-                            player_vec.sort_by(|(_, data_a), (_, data_b)| {
-                                data_b.kills.cmp(&data_a.kills)
-                            });
+                        ui.columns(8, |columns| {
+                            let player_vec = match &mode {
+                                Some(mode) => rank_players(&lobby, mode.rules.score),
+                                None => rank_players(&lobby, score_by_kills),
+                            };
 
                             player_vec.iter().enumerate().for_each(|(index, (id, data))| {
                                 columns[0].label(format!("#{}", index + 1));
                                 columns[1].label(format!("Player {}", id));
+                                if let Some(team) = data.team {
+                                    columns[2].label(RichText::new(team.name()).color(team_color(team)));
+                                }
+                                if let Some(mode) = &mode && mode.rules.extra_score.is_some() {
+                                    columns[3].label(format!("{}", (mode.rules.score)(data)));
+                                }
                                 columns[4].label(format!("{}", data.kills));
-                                columns[5].label(format!("{}", data.deaths));
+                                columns[5].label(format!("{}", data.assists));
+                                columns[6].label(format!("{}", data.deaths));
+                                columns[7].label(format!("{:.0}", data.damage_dealt));
                             });
                         });
                     });
                 });
         });
-}
\ No newline at end of file
+}
+
+fn capitalize(text: &str) -> String {
+    let mut chars = text.chars();
+    chars.next().map_or(String::new(), |first| first.to_uppercase().chain(chars).collect())
+}
@@ -6,8 +6,18 @@ use bevy_egui::{egui, EguiContexts};
 use bevy_egui::egui::{Align2, Color32, emath, Pos2, Shape, Stroke};
 use bevy_egui::egui::epaint::CircleShape;
 use crate::AppState;
+use crate::asset_loader::components::SpriteEnum;
+use crate::client_ui::arena_bounds::boundary_color;
+use crate::client_ui::control_points::control_point_color;
+use crate::client_ui::teams::team_color;
+use crate::client_ui::zone::ZONE_COLOR;
 use crate::display::camera::MainCamera;
+use crate::simulation::Lobby;
+use crate::simulation::map::MapData;
+use crate::simulation::server_sim::game_mode::battle_royale::BattleRoyaleZone;
+use crate::simulation::server_sim::game_mode::king_of_the_hill::ControlPoints;
 use crate::simulation::server_sim::player::{Player, You};
+use crate::simulation::team::Team;
 use crate::utils::ndc;
 use crate::utils::ui::DEFAULT_FRAME;
 
@@ -21,12 +31,18 @@ impl Plugin for MiniMapUIPlugin {
 
 const SIZE: f32 = 200.;
 
+#[allow(clippy::too_many_arguments)]
 fn minimap_display(
     mut contexts: EguiContexts,
-    player_q: Query<&Transform, With<Player>>,
+    player_q: Query<(&Player, &Transform)>,
+    object_q: Query<(&SpriteEnum, &Transform), Without<Player>>,
     you_q: Query<&Transform, With<You>>,
     window: Query<&Window, With<PrimaryWindow>>,
-    cam: Query<(&Camera, &GlobalTransform), With<MainCamera>>
+    cam: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    map: Option<Res<MapData>>,
+    zone: Option<Res<BattleRoyaleZone>>,
+    lobby: Res<Lobby>,
+    control_points: Option<Res<ControlPoints>>,
 ) {
     const OUTER_MARGIN: f32 = 10.;
     const MINIMAP_SCALE: f32 = 0.1;
@@ -63,13 +79,50 @@ fn minimap_display(
         response.layer_id,
         response.rect);
 
-    player_q.iter().for_each(|trans| {
+    let to_minimap = |point: Vec2| {
+        let screen_pos = minimap_center - (you_screen - world_to_screen(point)) * MINIMAP_SCALE;
+        Pos2::from([screen_pos.x, screen_pos.y])
+    };
+    let inner_painter = painter.with_clip_rect(
+        emath::Rect::from_min_max(Pos2::from([min.x, min.y]), Pos2::from([max.x, max.y])));
+
+    if let Some(map) = map {
+        let points = map.bounds.iter().map(|&point| to_minimap(point)).collect();
+        inner_painter.add(Shape::closed_line(points, Stroke { width: 2., color: boundary_color(map.boundary) }));
+    }
+
+    if let Some(zone) = zone {
+        let center = to_minimap(zone.center);
+        let radius = center.distance(to_minimap(zone.center + Vec2::X * zone.radius));
+        inner_painter.circle_stroke(center, radius, Stroke { width: 2., color: ZONE_COLOR });
+    }
+
+    if let Some(control_points) = control_points {
+        control_points.points.iter().for_each(|point| {
+            let center = to_minimap(point.center);
+            let radius = center.distance(to_minimap(point.center + Vec2::X * point.radius));
+            inner_painter.circle_filled(center, radius, control_point_color(point).linear_multiply(0.5));
+        });
+    }
+
+    object_q.iter().for_each(|(&sprite, trans)| {
+        let Some(team) = Team::of_flag_sprite(sprite) else { return; };
+        let center = to_minimap(trans.translation.truncate());
+        let center = Pos2::new(center.x.clamp(min.x, max.x), center.y.clamp(min.y, max.y));
+        painter.rect_filled(emath::Rect::from_center_size(center, emath::vec2(8., 8.)), 1., team_color(team));
+    });
+
+    player_q.iter().for_each(|(player, trans)| {
         let trans = trans.translation.truncate();
         let relative_screen_pos = you_screen - world_to_screen(trans);
         let scaled_relative_pos = relative_screen_pos * MINIMAP_SCALE;
         let screen_pos = (minimap_center - scaled_relative_pos).clamp(min, max);
 
-        let color = if trans == you_trans { Color32::WHITE } else { Color32::RED };
+        let color = match lobby.team_of(player.id) {
+            _ if trans == you_trans => Color32::WHITE,
+            Some(team) => team_color(team),
+            None => Color32::RED,
+        };
 
         let outside_bounds = [min, max].into_iter()
             .any(|v| screen_pos.x == v.x || screen_pos.y == v.y);
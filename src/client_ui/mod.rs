@@ -2,21 +2,51 @@ use bevy::app::App;
 use bevy::prelude::{EventReader, IntoSystemConfig, NextState, OnUpdate, Plugin, ResMut};
 use crate::AppState;
 use crate::asset_loader::AssetsLoadedEvent;
+use crate::client_ui::ammo::AmmoUIPlugin;
+use crate::client_ui::arena_bounds::ArenaBoundsUIPlugin;
+use crate::client_ui::beams::BeamsUIPlugin;
 use crate::client_ui::client_debug::ClientDebugUIPlugin;
+use crate::client_ui::control_points::ControlPointsUIPlugin;
+use crate::client_ui::explosions::ExplosionsUIPlugin;
+use crate::client_ui::feed::HudFeedUIPlugin;
 use crate::client_ui::health::HealthUiPlugin;
 use crate::client_ui::leaderboard::LeaderboardUIPlugin;
 use crate::client_ui::main_menu::MainMenuPlugin;
+use crate::client_ui::map_editor::MapEditorPlugin;
 use crate::client_ui::match_end_screen::MatchEndScreenUIPlugin;
 use crate::client_ui::match_length::MatchLengthUIPlugin;
 use crate::client_ui::minimap::MiniMapUIPlugin;
+use crate::client_ui::rounds::RoundsUIPlugin;
+use crate::client_ui::shields::ShieldsUIPlugin;
+use crate::client_ui::skills::SkillsUIPlugin;
+use crate::client_ui::teams::TeamUIPlugin;
+use crate::client_ui::upgrades::UpgradesUIPlugin;
+use crate::client_ui::waves::WavesUIPlugin;
+use crate::client_ui::weapons::WeaponsUIPlugin;
+use crate::client_ui::zone::ZoneUIPlugin;
 
+mod ammo;
+mod arena_bounds;
+mod beams;
 mod client_debug;
+mod control_points;
+mod explosions;
+mod feed;
 mod health;
 mod leaderboard;
 mod main_menu;
+mod map_editor;
 mod match_length;
 mod match_end_screen;
 mod minimap;
+mod rounds;
+mod shields;
+mod skills;
+mod teams;
+mod upgrades;
+mod waves;
+mod weapons;
+mod zone;
 
 pub struct ClientUIPlugin;
 
@@ -28,7 +58,22 @@ impl Plugin for ClientUIPlugin {
             .add_plugin(LeaderboardUIPlugin)
             .add_plugin(MatchLengthUIPlugin)
             .add_plugin(MatchEndScreenUIPlugin)
-            .add_plugin(MiniMapUIPlugin);
+            .add_plugin(MiniMapUIPlugin)
+            .add_plugin(MapEditorPlugin)
+            .add_plugin(ArenaBoundsUIPlugin)
+            .add_plugin(ZoneUIPlugin)
+            .add_plugin(TeamUIPlugin)
+            .add_plugin(HudFeedUIPlugin)
+            .add_plugin(ControlPointsUIPlugin)
+            .add_plugin(RoundsUIPlugin)
+            .add_plugin(WavesUIPlugin)
+            .add_plugin(ExplosionsUIPlugin)
+            .add_plugin(BeamsUIPlugin)
+            .add_plugin(AmmoUIPlugin)
+            .add_plugin(SkillsUIPlugin)
+            .add_plugin(UpgradesUIPlugin)
+            .add_plugin(ShieldsUIPlugin)
+            .add_plugin(WeaponsUIPlugin);
 
         app.add_system(main_menu_on_load.in_set(OnUpdate(AppState::Loading)));
 
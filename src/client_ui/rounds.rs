@@ -0,0 +1,83 @@
+use bevy::app::App;
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use bevy_egui::egui::{Align2, RichText};
+use crate::AppState;
+use crate::client_ui::teams::team_color;
+use crate::display::camera::Spectated;
+use crate::simulation::server_sim::game_mode::elimination::{RoundPhase, RoundState};
+use crate::simulation::server_sim::player::Player;
+use crate::utils::ui::{DEFAULT_FRAME, format_time};
+
+pub struct RoundsUIPlugin;
+
+impl Plugin for RoundsUIPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            (
+                rounds_ui,
+                spectating_ui,
+            ).in_set(OnUpdate(AppState::InGame))
+        );
+    }
+}
+
+fn rounds_ui(
+    mut contexts: EguiContexts,
+    rounds: Option<Res<RoundState>>,
+) {
+    let Some(rounds) = rounds else { return; };
+
+    let status = match rounds.phase {
+        RoundPhase::Intermission { time_remaining } if rounds.round == 0 =>
+            format!("Warmup {}", format_time(time_remaining)),
+        RoundPhase::Intermission { time_remaining } =>
+            format!("Next round in {}", format_time(time_remaining)),
+        RoundPhase::Starting => "Round starting".to_owned(),
+        RoundPhase::Playing { time_remaining } => format_time(time_remaining),
+    };
+
+    let last_round = match (rounds.phase, rounds.last_winner) {
+        (RoundPhase::Intermission { .. }, _) if rounds.round == 0 => None,
+        (RoundPhase::Intermission { .. }, Some(team)) =>
+            Some(RichText::new(format!("{} team took round {}", team.name(), rounds.round)).color(team_color(team))),
+        (RoundPhase::Intermission { .. }, None) => Some(RichText::new(format!("Round {} was a draw", rounds.round))),
+        _ => None,
+    };
+
+    egui::Area::new("Rounds Area")
+        .anchor(Align2::RIGHT_TOP, [0., 0.])
+        .show(contexts.ctx_mut(), |ui| {
+            DEFAULT_FRAME.outer_margin(10.0).show(ui, |ui| {
+                ui.vertical_centered(|ui| {
+                    ui.label(RichText::new(format!("Round {} (best of {})", rounds.round.max(1), rounds.best_of)).heading());
+                    ui.label(RichText::new(status).heading());
+                    if let Some(last_round) = last_round {
+                        ui.label(last_round);
+                    }
+                    rounds.wins.iter().for_each(|&(team, wins)| {
+                        ui.label(RichText::new(format!("{}: {} / {}", team.name(), wins, rounds.wins_needed()))
+                            .color(team_color(team)));
+                    });
+                });
+            });
+        });
+}
+
+fn spectating_ui(
+    mut contexts: EguiContexts,
+    spectated_q: Query<&Player, With<Spectated>>,
+) {
+    let Ok(player) = spectated_q.get_single() else { return; };
+
+    egui::Area::new("Spectating Area")
+        .anchor(Align2::CENTER_BOTTOM, [0., -10.])
+        .show(contexts.ctx_mut(), |ui| {
+            DEFAULT_FRAME.show(ui, |ui| {
+                ui.vertical_centered(|ui| {
+                    ui.label(RichText::new(format!("Spectating Player {}", player.id)).heading());
+                    ui.label("Space to switch");
+                });
+            });
+        });
+}
@@ -0,0 +1,120 @@
+use bevy::app::App;
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use bevy_egui::EguiContexts;
+use bevy_egui::egui::{Color32, LayerId, Pos2, Shape, Stroke};
+use crate::AppState;
+use crate::client_networking::RecvShieldBlockEvent;
+use crate::display::camera::MainCamera;
+use crate::simulation::SyncedObjects;
+use crate::simulation::server_sim::skill::ActiveShields;
+use crate::utils::ndc::world_to_screen;
+use crate::utils::prefabs::TANK_COLLIDER_RADIUS;
+
+const SHIELD_COLOR: Color32 = Color32::from_rgb(120, 200, 255);
+const SHIELD_DISTANCE: f32 = TANK_COLLIDER_RADIUS + 20.;
+const SHIELD_SEGMENTS: usize = 16;
+const BLOCK_LIFETIME: f32 = 0.3;
+const BLOCK_RADIUS: f32 = 30.;
+
+/// Draws raised shields in front of tanks, and flashes where they block a hit.
+pub struct ShieldsUIPlugin;
+
+impl Plugin for ShieldsUIPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<ShieldBlocks>()
+            .add_systems(
+                (
+                    draw_shields,
+                    add_shield_blocks.before(draw_shield_blocks),
+                    draw_shield_blocks,
+                ).in_set(OnUpdate(AppState::InGame))
+            )
+            .add_system(clear_shields.in_schedule(OnExit(AppState::InGame)));
+    }
+}
+
+#[derive(Resource, Default)]
+struct ShieldBlocks {
+    blocks: Vec<ShieldBlock>,
+}
+
+struct ShieldBlock {
+    position: Vec2,
+    age: f32,
+}
+
+fn draw_shields(
+    mut contexts: EguiContexts,
+    shields: Option<Res<ActiveShields>>,
+    objects: Res<SyncedObjects>,
+    tanks: Query<&GlobalTransform>,
+    camera_q: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    window_q: Query<&Window, With<PrimaryWindow>>,
+) {
+    let Some(shields) = shields else { return; };
+    let Ok((cam, cam_trans)) = camera_q.get_single() else { return; };
+    let Ok(window) = window_q.get_single() else { return; };
+
+    let painter = contexts.ctx_mut().layer_painter(LayerId::background());
+
+    shields.shields.iter().for_each(|(object_id, shield)| {
+        let Some(&entity) = objects.objects.get(object_id) else { return; };
+        let Ok(trans) = tanks.get(entity) else { return; };
+        let center = trans.translation().truncate();
+        let half_arc = shield.arc.to_radians() / 2.;
+
+        let points = (0..=SHIELD_SEGMENTS).map(|i| {
+            let angle = (i as f32 / SHIELD_SEGMENTS as f32 - 0.5) * 2. * half_arc;
+            let point = center + Vec2::from_angle(angle).rotate(shield.direction) * SHIELD_DISTANCE;
+            let screen = world_to_screen(point, window.height(), cam, cam_trans);
+            Pos2::new(screen.x, screen.y)
+        }).collect();
+
+        painter.add(Shape::line(points, Stroke::new(5., SHIELD_COLOR)));
+    });
+}
+
+fn add_shield_blocks(
+    mut events: EventReader<RecvShieldBlockEvent>,
+    mut blocks: ResMut<ShieldBlocks>,
+) {
+    events.iter().for_each(|e| {
+        blocks.blocks.push(ShieldBlock { position: e.position, age: 0. });
+    });
+}
+
+fn draw_shield_blocks(
+    mut contexts: EguiContexts,
+    mut blocks: ResMut<ShieldBlocks>,
+    camera_q: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    window_q: Query<&Window, With<PrimaryWindow>>,
+    time: Res<Time>,
+) {
+    blocks.blocks.iter_mut().for_each(|block| block.age += time.delta_seconds());
+    blocks.blocks.retain(|block| block.age < BLOCK_LIFETIME);
+    if blocks.blocks.is_empty() { return; }
+
+    let Ok((cam, cam_trans)) = camera_q.get_single() else { return; };
+    let Ok(window) = window_q.get_single() else { return; };
+
+    let painter = contexts.ctx_mut().layer_painter(LayerId::background());
+
+    blocks.blocks.iter().for_each(|block| {
+        let fade = 1. - block.age / BLOCK_LIFETIME;
+        let center = world_to_screen(block.position, window.height(), cam, cam_trans);
+        let edge = world_to_screen(block.position + Vec2::X * BLOCK_RADIUS, window.height(), cam, cam_trans);
+
+        painter.circle_filled(Pos2::new(center.x, center.y), center.distance(edge) * fade,
+                              Color32::WHITE.linear_multiply(fade));
+    });
+}
+
+fn clear_shields(
+    mut blocks: ResMut<ShieldBlocks>,
+    mut commands: Commands,
+) {
+    blocks.blocks.clear();
+    commands.remove_resource::<ActiveShields>();
+}
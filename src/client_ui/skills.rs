@@ -0,0 +1,81 @@
+use bevy::app::App;
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use bevy_egui::egui::{Align2, Color32, ProgressBar, RichText};
+use crate::AppState;
+use crate::client_networking::SKILL_BINDINGS;
+use crate::simulation::server_sim::player::You;
+use crate::simulation::server_sim::skill::{MAX_ENERGY, SkillEffect, SkillKind, SkillSlot, SkillStatus};
+use crate::utils::ui::DEFAULT_FRAME;
+
+const SKILL_WIDTH: f32 = 140.;
+const READY_COLOR: Color32 = Color32::from_rgb(80, 200, 120);
+const ACTIVE_COLOR: Color32 = Color32::from_rgb(80, 160, 255);
+const PASSIVE_COLOR: Color32 = Color32::from_rgb(120, 120, 120);
+
+/// Your equipped skills along the bottom of the screen, with their cooldowns or energy.
+pub struct SkillsUIPlugin;
+
+impl Plugin for SkillsUIPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_system(skills_ui.in_set(OnUpdate(AppState::InGame)))
+            .add_system(remove_skill_status.in_schedule(OnExit(AppState::InGame)));
+    }
+}
+
+fn skill_bar(slot: &SkillSlot, key: &str) -> ProgressBar {
+    let name = &slot.definition.display_name;
+    let (progress, color, text) = match slot.definition.kind {
+        SkillKind::Active { .. } if slot.active => {
+            let duration = match slot.definition.effect {
+                SkillEffect::Boost { duration, .. } => duration,
+                _ => slot.remaining,
+            };
+            (slot.remaining / duration, ACTIVE_COLOR, name.clone())
+        }
+        SkillKind::Active { cooldown } if slot.cooldown > 0. => {
+            (1. - slot.cooldown / cooldown, Color32::GRAY, format!("{name} {:.1}s", slot.cooldown))
+        }
+        SkillKind::Active { .. } => (1., READY_COLOR, format!("{name} [{key}]")),
+        SkillKind::Held { .. } => {
+            let color = if slot.active { ACTIVE_COLOR } else { READY_COLOR };
+            (slot.energy / MAX_ENERGY, color, format!("{name} [{key}]"))
+        }
+        SkillKind::Passive => (1., PASSIVE_COLOR, name.clone()),
+    };
+    ProgressBar::new(progress)
+        .fill(color)
+        .text(RichText::new(text).color(Color32::WHITE))
+        .desired_width(SKILL_WIDTH)
+}
+
+fn skills_ui(
+    mut contexts: EguiContexts,
+    status: Option<Res<SkillStatus>>,
+    you_q: Query<(), With<You>>,
+) {
+    let Some(status) = status else { return; };
+    if you_q.is_empty() { return; }
+
+    egui::Area::new("Skills Area")
+        .anchor(Align2::CENTER_BOTTOM, [0., 0.])
+        .show(contexts.ctx_mut(), |ui| {
+            DEFAULT_FRAME.outer_margin(10.0).show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    status.loadout.slots.iter().zip(SKILL_BINDINGS).for_each(|(slot, key)| {
+                        match slot {
+                            Some(slot) => { ui.add(skill_bar(slot, key)); }
+                            None => {
+                                ui.add(ProgressBar::new(0.).text(format!("Empty [{key}]")).desired_width(SKILL_WIDTH));
+                            }
+                        }
+                    });
+                });
+            });
+        });
+}
+
+fn remove_skill_status(mut commands: Commands) {
+    commands.remove_resource::<SkillStatus>();
+}
@@ -0,0 +1,61 @@
+use bevy::app::App;
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use bevy_egui::egui::{Align2, Button, Color32, RichText};
+use crate::AppState;
+use crate::client_networking::{ChooseTeamEvent, ClientId};
+use crate::simulation::Lobby;
+use crate::simulation::server_sim::game_mode::ActiveGameMode;
+use crate::simulation::server_sim::game_mode::teams::team_size;
+use crate::simulation::team::Team;
+use crate::utils::ui::DEFAULT_FRAME;
+
+pub struct TeamUIPlugin;
+
+impl Plugin for TeamUIPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(team_picker_ui.in_set(OnUpdate(AppState::InGame)));
+    }
+}
+
+pub fn team_color(team: Team) -> Color32 {
+    match team {
+        Team::Brown => Color32::from_rgb(170, 120, 70),
+        Team::Grey => Color32::from_rgb(160, 160, 160),
+        Team::Green => Color32::from_rgb(90, 170, 80),
+    }
+}
+
+fn team_picker_ui(
+    mut contexts: EguiContexts,
+    mut choice_writer: EventWriter<ChooseTeamEvent>,
+    mode: Option<Res<ActiveGameMode>>,
+    client_id: Option<Res<ClientId>>,
+    lobby: Res<Lobby>,
+) {
+    let Some(mode) = mode else { return; };
+    let Some(client_id) = client_id else { return; };
+    let Some(your_team) = lobby.team_of(client_id.0) else { return; };
+
+    egui::Area::new("Team Picker Area")
+        .anchor(Align2::LEFT_CENTER, [0., 0.])
+        .show(contexts.ctx_mut(), |ui| {
+            DEFAULT_FRAME.outer_margin(10.0).show(ui, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(RichText::new(format!("{} team", your_team.name()))
+                        .color(team_color(your_team))
+                        .heading());
+
+                    mode.teams.iter().copied().filter(|&team| team != your_team).for_each(|team| {
+                        // Switching is only allowed to a smaller team
+                        let can_join = team_size(&lobby, team) < team_size(&lobby, your_team);
+                        let button = Button::new(RichText::new(format!("Join {}", team.name()))
+                            .color(team_color(team)));
+                        if ui.add_enabled(can_join, button).clicked() {
+                            choice_writer.send(ChooseTeamEvent { team });
+                        }
+                    });
+                });
+            });
+        });
+}
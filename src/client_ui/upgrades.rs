@@ -0,0 +1,65 @@
+use bevy::app::App;
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use bevy_egui::egui::{Align2, Button, ProgressBar};
+use crate::AppState;
+use crate::client_networking::{ChooseUpgradeEvent, ClientId};
+use crate::simulation::Lobby;
+use crate::simulation::server_sim::leveling::{MAX_LEVEL, MAX_RANK, Progression, Stat};
+use crate::utils::ui::DEFAULT_FRAME;
+
+const UPGRADE_KEYS: [KeyCode; 6] = [KeyCode::Key1, KeyCode::Key2, KeyCode::Key3, KeyCode::Key4, KeyCode::Key5, KeyCode::Key6];
+
+/// Your level and experience in the bottom left, with the stats you can spend your points on.
+/// Upgrades are bought with the buttons or the number keys.
+pub struct UpgradesUIPlugin;
+
+impl Plugin for UpgradesUIPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(upgrades_ui.in_set(OnUpdate(AppState::InGame)));
+    }
+}
+
+fn upgrades_ui(
+    mut contexts: EguiContexts,
+    mut choice_writer: EventWriter<ChooseUpgradeEvent>,
+    keys: Res<Input<KeyCode>>,
+    client_id: Option<Res<ClientId>>,
+    lobby: Res<Lobby>,
+) {
+    let Some(client_id) = client_id else { return; };
+    let Some(data) = lobby.player_data.get(&client_id.0) else { return; };
+    let progression = &data.progression;
+
+    let can_upgrade = |stat: Stat| progression.points > 0 && progression.upgrades.rank(stat) < MAX_RANK;
+
+    Stat::ALL.iter().zip(UPGRADE_KEYS).for_each(|(&stat, key)| {
+        if keys.just_pressed(key) && can_upgrade(stat) {
+            choice_writer.send(ChooseUpgradeEvent { stat });
+        }
+    });
+
+    egui::Area::new("Upgrades Area")
+        .anchor(Align2::LEFT_BOTTOM, [0., 0.])
+        .show(contexts.ctx_mut(), |ui| {
+            DEFAULT_FRAME.outer_margin(10.0).show(ui, |ui| {
+                ui.set_width(180.);
+                ui.heading(format!("Level {}", progression.level));
+                if progression.level < MAX_LEVEL {
+                    let needed = Progression::xp_to_next_level(progression.level);
+                    ui.add(ProgressBar::new(progression.xp as f32 / needed as f32)
+                        .text(format!("{}/{} XP", progression.xp, needed)));
+                }
+                if progression.points == 0 { return; }
+
+                ui.label(format!("{} upgrade point{}", progression.points,
+                                 if progression.points != 1 { "s" } else { "" }));
+                Stat::ALL.iter().enumerate().for_each(|(i, &stat)| {
+                    let text = format!("[{}] {} {}/{}", i + 1, stat.name(), progression.upgrades.rank(stat), MAX_RANK);
+                    if ui.add_enabled(can_upgrade(stat), Button::new(text)).clicked() {
+                        choice_writer.send(ChooseUpgradeEvent { stat });
+                    }
+                });
+            });
+        });
+}
@@ -0,0 +1,43 @@
+use bevy::app::App;
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use bevy_egui::egui::{Align2, Color32, RichText};
+use crate::AppState;
+use crate::simulation::server_sim::game_mode::survival::{WavePhase, WaveState};
+use crate::utils::ui::{DEFAULT_FRAME, format_time};
+
+pub struct WavesUIPlugin;
+
+impl Plugin for WavesUIPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(waves_ui.in_set(OnUpdate(AppState::InGame)));
+    }
+}
+
+fn waves_ui(
+    mut contexts: EguiContexts,
+    waves: Option<Res<WaveState>>,
+) {
+    let Some(waves) = waves else { return; };
+
+    let status = match waves.phase {
+        WavePhase::Break { time_remaining } if waves.wave == 0 =>
+            format!("Warmup {}", format_time(time_remaining)),
+        WavePhase::Break { time_remaining } =>
+            format!("Next wave in {}", format_time(time_remaining)),
+        WavePhase::Fighting => format!("{} enemies left", waves.enemies_remaining),
+    };
+
+    egui::Area::new("Waves Area")
+        .anchor(Align2::RIGHT_TOP, [0., 0.])
+        .show(contexts.ctx_mut(), |ui| {
+            DEFAULT_FRAME.outer_margin(10.0).show(ui, |ui| {
+                ui.vertical_centered(|ui| {
+                    ui.label(RichText::new(format!("Wave {} / {}", waves.wave.max(1), waves.wave_count)).heading());
+                    ui.label(RichText::new(status).heading());
+                    ui.label(RichText::new(format!("Lives: {}", waves.lives))
+                        .color(if waves.lives == 0 { Color32::RED } else { Color32::WHITE }));
+                });
+            });
+        });
+}
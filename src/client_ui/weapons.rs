@@ -0,0 +1,70 @@
+use bevy::app::App;
+use bevy::prelude::*;
+use bevy_egui::{egui, EguiContexts};
+use bevy_egui::egui::Align2;
+use crate::AppState;
+use crate::asset_loader::resources::WeaponAssets;
+use crate::client_networking::{ChooseWeaponEvent, ClientId};
+use crate::simulation::Lobby;
+use crate::simulation::server_sim::game_mode::is_match_over;
+use crate::simulation::server_sim::player::You;
+use crate::simulation::server_sim::weapon::{DEFAULT_WEAPON, WeaponDefinitions};
+use crate::utils::ui::DEFAULT_FRAME;
+
+const TOGGLE_KEY: KeyCode = KeyCode::B;
+
+/// Lists every weapon to pick the one you spawn with. It's opened with B, and shown on its own
+/// while you're waiting to respawn.
+pub struct WeaponsUIPlugin;
+
+impl Plugin for WeaponsUIPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<WeaponPickerOpen>()
+            .add_system(weapon_picker_ui
+                .run_if(not(is_match_over))
+                .in_set(OnUpdate(AppState::InGame)));
+    }
+}
+
+#[derive(Resource, Default)]
+struct WeaponPickerOpen(bool);
+
+fn weapon_picker_ui(
+    mut contexts: EguiContexts,
+    mut open: ResMut<WeaponPickerOpen>,
+    mut choice_writer: EventWriter<ChooseWeaponEvent>,
+    keys: Res<Input<KeyCode>>,
+    (weapon_assets, definitions): (Res<WeaponAssets>, Res<Assets<WeaponDefinitions>>),
+    (client_id, lobby): (Option<Res<ClientId>>, Res<Lobby>),
+    you_q: Query<(), With<You>>,
+) {
+    if keys.just_pressed(TOGGLE_KEY) && !contexts.ctx_mut().wants_keyboard_input() {
+        open.0 = !open.0;
+    }
+    if !open.0 && !you_q.is_empty() { return; }
+
+    let Some(client_id) = client_id else { return; };
+    let Some(data) = lobby.player_data.get(&client_id.0) else { return; };
+    let Some(definitions) = definitions.get(&weapon_assets.handle) else { return; };
+    let chosen = data.weapon.as_deref().unwrap_or(DEFAULT_WEAPON);
+
+    let mut weapons = definitions.weapons.iter().collect::<Vec<_>>();
+    weapons.sort_by(|(_, a), (_, b)| a.display_name.cmp(&b.display_name));
+
+    egui::Area::new("Weapon Picker Area")
+        .anchor(Align2::CENTER_CENTER, [0., 0.])
+        .show(contexts.ctx_mut(), |ui| {
+            DEFAULT_FRAME.outer_margin(10.0).show(ui, |ui| {
+                ui.heading("Weapon");
+                ui.small("Used from your next spawn on");
+                weapons.iter().for_each(|(name, weapon)| {
+                    if ui.selectable_label(name.as_str() == chosen, &weapon.display_name).clicked() &&
+                        name.as_str() != chosen {
+                        choice_writer.send(ChooseWeaponEvent { weapon: name.to_string() });
+                    }
+                });
+                ui.small(format!("[{:?}] to close", TOGGLE_KEY));
+            });
+        });
+}
@@ -0,0 +1,87 @@
+use bevy::app::App;
+use bevy::prelude::*;
+use bevy::window::PrimaryWindow;
+use bevy_egui::{egui, EguiContexts};
+use bevy_egui::egui::{Align2, Color32, LayerId, Pos2, RichText, Stroke};
+use crate::AppState;
+use crate::display::camera::MainCamera;
+use crate::simulation::server_sim::game_mode::battle_royale::BattleRoyaleZone;
+use crate::simulation::server_sim::player::You;
+use crate::utils::ndc::world_to_screen;
+use crate::utils::ui::{DEFAULT_FRAME, format_time};
+
+pub struct ZoneUIPlugin;
+
+impl Plugin for ZoneUIPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            (
+                draw_zone,
+                zone_timer_ui,
+            ).in_set(OnUpdate(AppState::InGame))
+        );
+    }
+}
+
+pub const ZONE_COLOR: Color32 = Color32::from_rgb(60, 140, 255);
+
+fn draw_zone(
+    mut contexts: EguiContexts,
+    zone: Option<Res<BattleRoyaleZone>>,
+    camera_q: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
+    window_q: Query<&Window, With<PrimaryWindow>>,
+) {
+    let Some(zone) = zone else { return; };
+    let Ok((cam, cam_trans)) = camera_q.get_single() else { return; };
+    let Ok(window) = window_q.get_single() else { return; };
+
+    let to_screen = |center: Vec2, radius: f32| {
+        let screen_center = world_to_screen(center, window.height(), cam, cam_trans);
+        let screen_edge = world_to_screen(center + Vec2::X * radius, window.height(), cam, cam_trans);
+        (Pos2::new(screen_center.x, screen_center.y), screen_center.distance(screen_edge))
+    };
+
+    let painter = contexts.ctx_mut().layer_painter(LayerId::background());
+
+    let (center, radius) = to_screen(zone.center, zone.radius);
+    painter.circle_stroke(center, radius, Stroke::new(4., ZONE_COLOR));
+
+    if !zone.is_closed() {
+        let (center, radius) = to_screen(zone.target_center, zone.target_radius);
+        painter.circle_stroke(center, radius, Stroke::new(2., Color32::WHITE));
+    }
+}
+
+fn zone_timer_ui(
+    mut contexts: EguiContexts,
+    zone: Option<Res<BattleRoyaleZone>>,
+    you_q: Query<&Transform, With<You>>,
+) {
+    let Some(zone) = zone else { return; };
+
+    let status = if zone.is_closed() {
+        "Zone closed".to_owned()
+    } else if zone.shrinking {
+        format!("Zone closing {}", format_time(zone.time_remaining))
+    } else if zone.is_warmup() {
+        format!("Warmup {}", format_time(zone.time_remaining))
+    } else {
+        format!("Zone closes in {}", format_time(zone.time_remaining))
+    };
+
+    let outside = you_q.get_single()
+        .map_or(false, |trans| !zone.contains(trans.translation.truncate()));
+
+    egui::Area::new("Zone Timer Area")
+        .anchor(Align2::RIGHT_TOP, [0., 0.])
+        .show(contexts.ctx_mut(), |ui| {
+            DEFAULT_FRAME.outer_margin(10.0).show(ui, |ui| {
+                ui.vertical_centered(|ui| {
+                    ui.label(RichText::new(status).heading());
+                    if outside {
+                        ui.label(RichText::new("Outside the zone!").color(Color32::RED));
+                    }
+                });
+            });
+        });
+}
@@ -1,7 +1,8 @@
 use std::iter::zip;
 use bevy::app::{App, Plugin};
 use bevy::ecs::reflect::ReflectComponent;
-use bevy::prelude::{Camera, Commands, Component, debug, EventReader, IntoSystemConfig, Query, Reflect, Res, Transform, With, Without};
+use bevy::input::Input;
+use bevy::prelude::{Camera, Commands, Component, debug, Entity, EventReader, IntoSystemConfig, KeyCode, Query, Reflect, Res, Transform, With, Without};
 use bevy::time::Time;
 use serde::{Deserialize, Serialize};
 use crate::client_networking::ClientId;
@@ -9,8 +10,9 @@ use crate::ClientSet::ClientUpdate;
 use crate::display::sprite_updater::CAMERA_LAYER;
 use crate::utils::networking::{is_client_exe};
 use crate::simulation::server_sim::player::components::You;
+use crate::simulation::server_sim::player::Player;
 use crate::simulation::events::OnPlayerSpawnEvent;
-use crate::simulation::SyncedObjects;
+use crate::simulation::{Lobby, SyncedObjects};
 
 static CAMERA_SMOOTHING: f32 = 2.;
 
@@ -18,23 +20,30 @@ static CAMERA_SMOOTHING: f32 = 2.;
 #[reflect(Component)]
 pub struct MainCamera;
 
+/// The tank the camera follows while you don't have one.
+#[derive(Component)]
+pub struct Spectated;
+
 pub struct GameCameraPlugin;
 
 impl Plugin for GameCameraPlugin {
     fn build(&self, app: &mut App) {
         app.register_type::<MainCamera>()
             .add_system(camera_move)
-            .add_system(you_tag_adder.run_if(is_client_exe).after(ClientUpdate));
+            .add_system(you_tag_adder.run_if(is_client_exe).after(ClientUpdate))
+            .add_system(spectate_target.run_if(is_client_exe).after(ClientUpdate));
     }
 }
 
 fn camera_move(
     player_query: Query<&Transform, With<You>>,
-    mut camera_query: Query<&mut Transform, (With<Camera>, Without<You>)>,
+    spectated_query: Query<&Transform, With<Spectated>>,
+    mut camera_query: Query<&mut Transform, (With<Camera>, Without<You>, Without<Spectated>)>,
     time: Res<Time>,
 ) {
+    let target = player_query.get_single().or_else(|_| spectated_query.get_single());
     for (player_trans, mut cam_trans) in zip(
-        player_query.iter(),
+        target.into_iter(),
         camera_query.iter_mut())
     {
         cam_trans.translation = cam_trans.translation.lerp(
@@ -61,3 +70,47 @@ fn you_tag_adder(
         }
     }
 }
+
+/// While you're dead the camera follows a living teammate, or any survivor once your team is out.
+/// Space switches to the next one.
+fn spectate_target(
+    you_q: Query<(), With<You>>,
+    players: Query<(Entity, &Player, Option<&Spectated>)>,
+    keys: Res<Input<KeyCode>>,
+    client: Option<Res<ClientId>>,
+    lobby: Res<Lobby>,
+    mut commands: Commands,
+) {
+    let current = players.iter()
+        .find(|(_, _, spectated)| spectated.is_some())
+        .map(|(entity, player, _)| (entity, player.id));
+
+    if !you_q.is_empty() {
+        if let Some((entity, _)) = current {
+            commands.entity(entity).remove::<Spectated>();
+        }
+        return;
+    }
+    if current.is_some() && !keys.just_pressed(KeyCode::Space) { return; }
+    let Some(client) = client else { return; };
+
+    let mut candidates = players.iter()
+        .filter(|(_, player, _)| player.id != client.0 && lobby.player_data.contains_key(&player.id))
+        .map(|(entity, player, _)| (player.id, entity))
+        .collect::<Vec<_>>();
+    if candidates.iter().any(|(id, _)| lobby.are_teammates(*id, client.0)) {
+        candidates.retain(|(id, _)| lobby.are_teammates(*id, client.0));
+    }
+    candidates.sort_by_key(|(id, _)| *id);
+
+    let next = candidates.iter()
+        .find(|(id, _)| current.map_or(true, |(_, current_id)| *id > current_id))
+        .or(candidates.first());
+    let Some(&(_, next)) = next else { return; };
+
+    if let Some((entity, _)) = current {
+        if entity == next { return; }
+        commands.entity(entity).remove::<Spectated>();
+    }
+    commands.entity(next).insert(Spectated);
+}
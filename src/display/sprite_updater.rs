@@ -1,13 +1,14 @@
 use bevy::app::App;
 use bevy::asset::{Assets, Handle};
 use bevy::math::{IVec2, Vec2};
-use bevy::prelude::{Camera, Commands, Component, default, Entity, GlobalTransform, Image, IntoSystemConfig, OnUpdate, Plugin, Query, Res, ResMut, Resource, Sprite, SpriteBundle, Transform, TransformBundle, Window, With};
+use bevy::prelude::{Camera, Commands, Component, Condition, default, Entity, GlobalTransform, Image, in_state, IntoSystemConfig, IntoSystemAppConfig, OnExit, Plugin, Query, Res, ResMut, Resource, Sprite, SpriteBundle, Transform, TransformBundle, Window, With};
 use bevy::utils::{HashSet};
 use bevy::window::PrimaryWindow;
 use crate::AppState;
 use crate::asset_loader::components::SpriteEnum;
 use crate::asset_loader::resources::SpriteAssets;
 use crate::display::camera::MainCamera;
+use crate::simulation::map::MapData;
 use crate::utils::ndc::{camera_world_bounds, ScreenSize};
 
 pub const BACKGROUND_LAYER: f32 = -100.;
@@ -15,6 +16,8 @@ pub const PLAYER_LAYER: f32 = 0.;
 pub const BULLET_LAYER: f32 = 1.;
 pub const CAMERA_LAYER: f32 = 100.;
 
+const BACKGROUND_MAP_PADDING: f32 = 2000.;
+
 pub struct SpriteUpdatePlugin;
 
 impl Plugin for SpriteUpdatePlugin {
@@ -24,8 +27,10 @@ impl Plugin for SpriteUpdatePlugin {
         app.add_systems((
             update_sprite_handle,
             auto_sort_system,
-            background_spawner.in_set(OnUpdate(AppState::InGame)),
-        ));
+            background_spawner.run_if(in_state(AppState::InGame).or_else(in_state(AppState::MapEditor))),
+        ))
+            .add_system(clear_background_tiles.in_schedule(OnExit(AppState::InGame)))
+            .add_system(clear_background_tiles.in_schedule(OnExit(AppState::MapEditor)));
     }
 }
 
@@ -87,12 +92,17 @@ pub struct BackgroundTilePositions {
     pub set: HashSet<IVec2>,
 }
 
+fn clear_background_tiles(mut tile_pos_set: ResMut<BackgroundTilePositions>) {
+    tile_pos_set.set.clear();
+}
+
 fn background_spawner(
     camera: Query<(&Camera, &GlobalTransform), With<MainCamera>>,
     window: Query<&Window, With<PrimaryWindow>>,
     assets: Res<SpriteAssets>,
     image_assets: Res<Assets<Image>>,
     mut tile_pos_set: ResMut<BackgroundTilePositions>,
+    map: Option<Res<MapData>>,
     mut commands: Commands,
 ) {
     let Ok((cam, trans)) = camera.get_single() else { return; };
@@ -139,7 +149,18 @@ fn background_spawner(
         background_size.x,
     );
 
-    points_within_camera.into_iter().filter(|p| tile_pos_set.set.insert(p.as_ivec2()))
+    // Only tile a margin around the arena rather than forever
+    let padded_bounds = map.map(|map| {
+        let [map_min, map_max] = map.bounding_box();
+        let padding = Vec2::splat(BACKGROUND_MAP_PADDING);
+        [map_min - padding, map_max + padding]
+    });
+    let in_map = |p: &Vec2| match padded_bounds {
+        Some([map_min, map_max]) => p.cmpge(map_min).all() && p.cmple(map_max).all(),
+        None => true,
+    };
+
+    points_within_camera.into_iter().filter(in_map).filter(|p| tile_pos_set.set.insert(p.as_ivec2()))
         .for_each(|p| {
             commands.spawn(SpriteEnum::Background)
                 .insert(TransformBundle::from_transform(
@@ -98,6 +98,7 @@ pub enum AppState {
     MainMenu,
     InGame,
     Paused,
+    MapEditor,
 }
 
 #[allow(clippy::enum_variant_names)]
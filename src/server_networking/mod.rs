@@ -25,8 +25,23 @@ impl Plugin for ServerNetworkingPlugin {
                     on_player_spawn,
                     update_match_timer,
                     update_health,
-                    update_kill_death_count,
+                    update_player_data,
+                    update_map,
+                    update_zone,
+                    update_game_mode,
+                    update_flag_feed,
+                    update_control_points,
+                    update_rounds,
+                    update_waves,
                 ).in_set(ServerSend).before(on_object_despawn))
+            .add_system(update_bullet_bounces.in_set(ServerSend).before(on_object_despawn))
+            .add_system(update_explosions.in_set(ServerSend).before(on_object_despawn))
+            .add_system(update_beams.in_set(ServerSend).before(on_object_despawn))
+            .add_system(server_send_mines.in_set(ServerSend).before(on_object_despawn))
+            .add_system(server_send_weapon_status.in_set(ServerSend).before(on_object_despawn))
+            .add_system(server_send_skill_status.in_set(ServerSend).before(on_object_despawn))
+            .add_system(update_shields.in_set(ServerSend).before(on_object_despawn))
+            .add_system(update_shield_blocks.in_set(ServerSend).before(on_object_despawn))
             .add_system(on_object_despawn.in_set(ServerSend));
 
     }
@@ -1,21 +1,37 @@
-use bevy::prelude::{Children, Commands, EventReader, EventWriter, GlobalTransform, Query, Res, ResMut, Transform, With};
+use bevy::prelude::{Assets, Children, Commands, EventReader, EventWriter, GlobalTransform, Query, Res, ResMut, Transform, Vec2, With, Without};
 use bevy::log::info;
 use std::mem::size_of;
 use bevy::tasks::{ParallelSlice, TaskPool};
 use bevy_rapier2d::dynamics::Velocity;
-use bevy::utils::HashMap;
+use bevy::utils::{HashMap, HashSet};
 use bevy_quinnet::server::{ConnectionEvent, ConnectionLostEvent, Server};
 use bevy_quinnet::shared::channel::ChannelId;
 use crate::asset_loader::components::SpriteEnum;
 use crate::simulation::PlayerData;
 use crate::client_networking::ClientMessage;
-use crate::simulation::events::{OnObjectDespawnEvent, OnPlayerConnectEvent, OnPlayerSpawnEvent};
-use crate::utils::networking::messages::{PhysicsObjData, ServerMessage};
+use crate::simulation::events::{OnObjectDespawnEvent, OnPlayerConnectEvent, OnPlayerDataChangedEvent, OnPlayerSpawnEvent};
+use crate::utils::networking::messages::{PhysicsObjData, PlayerId, ServerMessage};
 use crate::simulation::{Lobby, ObjectId};
 use crate::simulation::Object;
 use crate::simulation::server_sim::init::OnInitEvent;
-use crate::simulation::server_sim::match_ffa::{MatchTimer};
-use crate::simulation::server_sim::player::{OnHealthChangedEvent, OnKillEvent, Player, PlayerInput, PlayerTurret};
+use crate::simulation::map::MapSource;
+use crate::simulation::server_sim::game_mode::{ActiveGameMode, MatchOutcome, MatchTimer, OnMatchEndEvent};
+use crate::simulation::server_sim::game_mode::battle_royale::BattleRoyaleZone;
+use crate::simulation::server_sim::game_mode::capture_the_flag::FlagEvent;
+use crate::simulation::server_sim::game_mode::king_of_the_hill::ControlPoints;
+use crate::simulation::server_sim::game_mode::elimination::RoundState;
+use crate::simulation::server_sim::game_mode::survival::WaveState;
+use crate::simulation::server_sim::game_mode::teams::OnTeamChoiceEvent;
+use crate::simulation::server_sim::bullet::{OnBulletBounceEvent, OnReloadEvent, OnShieldBlockEvent};
+use crate::simulation::server_sim::explosion::OnExplosionEvent;
+use crate::simulation::server_sim::hitscan::OnBeamEvent;
+use crate::simulation::server_sim::leveling::OnUpgradeChoiceEvent;
+use crate::simulation::server_sim::mine::{Mine, OnDropMineEvent};
+use crate::simulation::server_sim::skill::{ActiveShields, ShieldState, SkillLoadout, SkillStatus};
+use crate::simulation::server_sim::spawn::SpawnProtection;
+use crate::simulation::server_sim::weapon::{OnWeaponChoiceEvent, WeaponDefinitions};
+use crate::asset_loader::resources::WeaponAssets;
+use crate::simulation::server_sim::player::{OnAssistEvent, OnHealthChangedEvent, OnKillEvent, OnPlayerDeathEvent, Player, PlayerInput, PlayerTurret};
 use crate::simulation::SyncedObjects;
 use crate::utils::commands::despawn::CustomDespawnExt;
 use crate::utils::commands::try_insert::TryInsertExt;
@@ -25,6 +41,13 @@ pub fn server_recv(
     mut commands: Commands,
     lobby: Res<Lobby>,
     objects: Res<SyncedObjects>,
+    (mut team_choice_writer, mut upgrade_choice_writer, mut weapon_choice_writer):
+    (
+        EventWriter<OnTeamChoiceEvent>,
+        EventWriter<OnUpgradeChoiceEvent>,
+        EventWriter<OnWeaponChoiceEvent>,
+    ),
+    (mut mine_writer, mut reload_writer): (EventWriter<OnDropMineEvent>, EventWriter<OnReloadEvent>),
 ) {
     let endpoint = server.endpoint_mut();
     for client_id in endpoint.clients().into_iter() {
@@ -37,6 +60,21 @@ pub fn server_recv(
                         commands.entity(entity).try_insert(PlayerInput::from(input));
                     }
                 }
+                ClientMessage::ChooseTeam { team } => {
+                    team_choice_writer.send(OnTeamChoiceEvent { player_id: client_id, team });
+                }
+                ClientMessage::UpgradeStat { stat } => {
+                    upgrade_choice_writer.send(OnUpgradeChoiceEvent { player_id: client_id, stat });
+                }
+                ClientMessage::ChooseWeapon { weapon } => {
+                    weapon_choice_writer.send(OnWeaponChoiceEvent { player_id: client_id, weapon });
+                }
+                ClientMessage::DropMine => {
+                    mine_writer.send(OnDropMineEvent { player_id: client_id });
+                }
+                ClientMessage::Reload => {
+                    reload_writer.send(OnReloadEvent { player_id: client_id });
+                }
             }
         }
     }
@@ -44,9 +82,10 @@ pub fn server_recv(
 
 const UNRELIABLE_BYTE_MAX: usize = 3000;
 
+/// Mines are left out, they're only sent to their owner's team in `server_send_mines`.
 pub fn server_send_phys_obj(
     server: Res<Server>,
-    query: Query<(&Object, &Transform, Option<&Velocity>, Option<&SpriteEnum>)>,
+    query: Query<(&Object, &Transform, Option<&Velocity>, Option<&SpriteEnum>), Without<Mine>>,
 ) {
     let objects: Vec<(ObjectId, PhysicsObjData)> = query.iter()
         .map(|(object, &trans, vel, sprite)| {
@@ -71,6 +110,102 @@ pub fn server_send_phys_obj(
         });
 }
 
+pub fn server_send_mines(
+    server: Res<Server>,
+    mines: Query<(&Object, &Mine, &Transform, &SpriteEnum)>,
+    lobby: Res<Lobby>,
+) {
+    if mines.is_empty() { return; }
+
+    server.endpoint().clients().into_iter().for_each(|client_id| {
+        let objects: HashMap<ObjectId, PhysicsObjData> = mines.iter()
+            .filter(|(_, mine, _, _)| mine.is_visible_to(client_id, &lobby))
+            .map(|(object, _, &trans, &sprite)| {
+                (object.id, PhysicsObjData {
+                    transform: trans,
+                    velocity: Vec2::ZERO,
+                    sprite: Some(sprite),
+                })
+            }).collect();
+        if objects.is_empty() { return; }
+
+        server.endpoint().send_message_on(
+            client_id,
+            ChannelId::Unreliable,
+            ServerMessage::PhysObjUpdate { objects },
+        ).unwrap();
+    });
+}
+
+/// Every player is only sent the state of their own weapon.
+pub fn server_send_weapon_status(
+    server: Res<Server>,
+    player_q: Query<(&Player, &Children)>,
+    turr_q: Query<&PlayerTurret>,
+    weapon_assets: Res<WeaponAssets>,
+    definitions: Res<Assets<WeaponDefinitions>>,
+) {
+    let clients = server.endpoint().clients();
+    player_q.iter()
+        .filter(|(player, _)| clients.contains(&player.id))
+        .for_each(|(player, children)| {
+            let Some(turret) = children.iter().find_map(|&ent| turr_q.get(ent).ok()) else { return; };
+            let Some(weapon) = weapon_assets.get(&definitions, &turret.weapon) else { return; };
+
+            server.endpoint().send_message_on(
+                player.id,
+                ChannelId::Unreliable,
+                ServerMessage::WeaponStatusUpdate { status: turret.state.status(weapon) },
+            ).unwrap();
+        });
+}
+
+/// Every player is only sent the cooldowns of their own skills.
+pub fn server_send_skill_status(
+    server: Res<Server>,
+    player_q: Query<(&Player, &SkillLoadout)>,
+) {
+    let clients = server.endpoint().clients();
+    player_q.iter()
+        .filter(|(player, _)| clients.contains(&player.id))
+        .for_each(|(player, loadout)| {
+            server.endpoint().send_message_on(
+                player.id,
+                ChannelId::Unreliable,
+                ServerMessage::SkillStatusUpdate { status: SkillStatus { loadout: loadout.clone() } },
+            ).unwrap();
+        });
+}
+
+pub fn update_shields(
+    server: Res<Server>,
+    player_q: Query<(&Object, &SkillLoadout, Option<&SpawnProtection>)>,
+) {
+    let shields = player_q.iter()
+        .filter_map(|(object, loadout, protection)| {
+            // Spawn protection is shown as a shield all the way around the tank
+            let arc = if protection.is_some() { Some(360.) } else { loadout.shield_arc() }?;
+            Some((object.id, ShieldState { direction: loadout.aim, arc }))
+        })
+        .collect();
+    server.endpoint().broadcast_message_on(
+        ChannelId::Unreliable,
+        ServerMessage::ShieldsUpdate { shields: ActiveShields { shields } },
+    ).unwrap();
+}
+
+pub fn update_shield_blocks(
+    mut block_events: EventReader<OnShieldBlockEvent>,
+    server: Res<Server>,
+) {
+    block_events.iter().for_each(|e| {
+        server.endpoint().broadcast_message_on(
+            ChannelId::Unreliable,
+            ServerMessage::ShieldBlock { position: e.position }
+        ).unwrap();
+    });
+}
+
 pub fn server_send_turrets(
     server: Res<Server>,
     player_q: Query<(&Object, &Children), With<Player>>,
@@ -91,37 +226,31 @@ pub fn server_send_turrets(
     ).unwrap();
 }
 
-pub fn update_kill_death_count(
+pub fn update_player_data(
     mut kill_events: EventReader<OnKillEvent>,
+    mut assist_events: EventReader<OnAssistEvent>,
+    mut death_events: EventReader<OnPlayerDeathEvent>,
     mut init_events: EventReader<OnInitEvent>,
-    mut lobby: ResMut<Lobby>,
+    mut changed_events: EventReader<OnPlayerDataChangedEvent>,
+    lobby: Res<Lobby>,
     server: Res<Server>,
 ) {
-    kill_events.iter().for_each(|e| {
-        if let Some(mut attacker_data) = lobby.player_data.get_mut(&e.attacker_id) {
-            attacker_data.kills += 1;
-            server.endpoint().broadcast_message_on(
-                ChannelId::UnorderedReliable,
-                ServerMessage::PlayerDataUpdate { player_id: e.attacker_id, data: attacker_data.clone() },
-            ).unwrap();
-        }
-        if let Some(victim_data) = lobby.player_data.get_mut(&e.victim_id) {
-            victim_data.deaths += 1;
-            server.endpoint().broadcast_message_on(
-                ChannelId::UnorderedReliable,
-                ServerMessage::PlayerDataUpdate { player_id: e.victim_id, data: victim_data.clone() },
-            ).unwrap();
-        }
-    });
+    let changed = kill_events.iter().map(|e| e.attacker_id)
+        .chain(assist_events.iter().map(|e| e.assister_id))
+        .chain(death_events.iter().map(|e| e.player_id))
+        .chain(changed_events.iter().map(|e| e.player_id))
+        .collect::<HashSet<PlayerId>>();
 
-    init_events.iter().for_each(|_| {
-        lobby.player_data.iter().for_each(|(&player_id, data)| {
+    let all = init_events.iter().next().is_some();
+
+    lobby.player_data.iter()
+        .filter(|(player_id, _)| all || changed.contains(player_id))
+        .for_each(|(&player_id, data)| {
             server.endpoint().broadcast_message_on(
                 ChannelId::UnorderedReliable,
-                ServerMessage::PlayerDataUpdate {player_id, data: data.clone() }
+                ServerMessage::PlayerDataUpdate { player_id, data: data.clone() },
             ).unwrap();
-        })
-    });
+        });
 }
 
 pub fn update_health(
@@ -140,15 +269,28 @@ pub fn update_health(
     });
 }
 
+/// Mines only go away for the players who were sent them in `server_send_mines`.
 pub fn on_object_despawn(
     mut despawn_event: EventReader<OnObjectDespawnEvent>,
     server: Res<Server>,
+    lobby: Res<Lobby>,
 ) {
     despawn_event.iter().for_each(|e| {
-        server.endpoint().broadcast_message_on(
-            ChannelId::UnorderedReliable,
-            ServerMessage::ObjectDespawn { object_id: e.id },
-        ).unwrap();
+        match e.mine_owner {
+            Some(owner_id) => server.endpoint().clients().into_iter()
+                .filter(|&client_id| Mine::is_visible_from(owner_id, client_id, &lobby))
+                .for_each(|client_id| {
+                    server.endpoint().send_message_on(
+                        client_id,
+                        ChannelId::UnorderedReliable,
+                        ServerMessage::ObjectDespawn { object_id: e.id },
+                    ).unwrap();
+                }),
+            None => server.endpoint().broadcast_message_on(
+                ChannelId::UnorderedReliable,
+                ServerMessage::ObjectDespawn { object_id: e.id },
+            ).unwrap(),
+        }
     });
 }
 
@@ -157,11 +299,40 @@ pub fn on_client_connect(
     mut spawn_event_writer: EventWriter<OnPlayerConnectEvent>,
     server: Res<Server>,
     lobby: Res<Lobby>,
+    map: Option<Res<MapSource>>,
+    (mode, outcome): (Option<Res<ActiveGameMode>>, Option<Res<MatchOutcome>>),
     player_query: Query<(&GlobalTransform, &Object), With<Player>>,
 ) {
     for &ConnectionEvent { id } in connection_events.iter() {
         info!("Player {id} Connected.");
 
+        if let Some(map) = map.as_deref() {
+            server.endpoint().send_message_on(
+                id,
+                ChannelId::UnorderedReliable,
+                ServerMessage::MapInit { source: map.clone() },
+            ).unwrap();
+        }
+
+        if let Some(mode) = mode.as_deref() {
+            server.endpoint().send_message_on(
+                id,
+                ChannelId::UnorderedReliable,
+                ServerMessage::GameModeInit {
+                    name: mode.rules.name.to_owned(),
+                    teams: mode.teams.clone(),
+                },
+            ).unwrap();
+        }
+
+        if let Some(outcome) = outcome.as_deref() {
+            server.endpoint().send_message_on(
+                id,
+                ChannelId::UnorderedReliable,
+                ServerMessage::MatchEnd { outcome: outcome.clone() },
+            ).unwrap();
+        }
+
         server.endpoint().broadcast_message_on(
             ChannelId::UnorderedReliable,
             ServerMessage::PlayerConnected {
@@ -251,3 +422,135 @@ pub fn update_match_timer(
         ServerMessage::MatchTimerMsg { time_remaining: match_timer.time_remaining }
     ).unwrap();
 }
+
+pub fn update_zone(
+    zone: Option<Res<BattleRoyaleZone>>,
+    server: Res<Server>,
+) {
+    let Some(zone) = zone else { return; };
+    server.endpoint().broadcast_message_on(
+        ChannelId::Unreliable,
+        ServerMessage::ZoneUpdate { zone: zone.clone() }
+    ).unwrap();
+}
+
+pub fn update_control_points(
+    control_points: Option<Res<ControlPoints>>,
+    server: Res<Server>,
+) {
+    let Some(control_points) = control_points else { return; };
+    server.endpoint().broadcast_message_on(
+        ChannelId::Unreliable,
+        ServerMessage::ControlPointsUpdate { control_points: control_points.clone() }
+    ).unwrap();
+}
+
+pub fn update_rounds(
+    rounds: Option<Res<RoundState>>,
+    server: Res<Server>,
+) {
+    let Some(rounds) = rounds else { return; };
+    server.endpoint().broadcast_message_on(
+        ChannelId::Unreliable,
+        ServerMessage::RoundUpdate { rounds: rounds.clone() }
+    ).unwrap();
+}
+
+pub fn update_waves(
+    waves: Option<Res<WaveState>>,
+    server: Res<Server>,
+) {
+    let Some(waves) = waves else { return; };
+    server.endpoint().broadcast_message_on(
+        ChannelId::Unreliable,
+        ServerMessage::WaveUpdate { waves: waves.clone() }
+    ).unwrap();
+}
+
+pub fn update_bullet_bounces(
+    mut bounce_events: EventReader<OnBulletBounceEvent>,
+    server: Res<Server>,
+) {
+    bounce_events.iter().for_each(|e| {
+        server.endpoint().broadcast_message_on(
+            ChannelId::UnorderedReliable,
+            ServerMessage::BulletBounce { object_id: e.object_id, position: e.position, velocity: e.velocity }
+        ).unwrap();
+    });
+}
+
+pub fn update_explosions(
+    mut explosion_events: EventReader<OnExplosionEvent>,
+    server: Res<Server>,
+) {
+    explosion_events.iter().for_each(|e| {
+        server.endpoint().broadcast_message_on(
+            ChannelId::UnorderedReliable,
+            ServerMessage::Explosion { position: e.position, radius: e.radius }
+        ).unwrap();
+    });
+}
+
+pub fn update_beams(
+    mut beam_events: EventReader<OnBeamEvent>,
+    server: Res<Server>,
+) {
+    beam_events.iter().for_each(|e| {
+        server.endpoint().broadcast_message_on(
+            ChannelId::Unreliable,
+            ServerMessage::Beam { start: e.start, end: e.end }
+        ).unwrap();
+    });
+}
+
+pub fn update_game_mode(
+    mut init_events: EventReader<OnInitEvent>,
+    mut end_events: EventReader<OnMatchEndEvent>,
+    mode: Option<Res<ActiveGameMode>>,
+    server: Res<Server>,
+) {
+    if let Some(mode) = mode {
+        init_events.iter().for_each(|_| {
+            server.endpoint().broadcast_message_on(
+                ChannelId::UnorderedReliable,
+                ServerMessage::GameModeInit {
+                    name: mode.rules.name.to_owned(),
+                    teams: mode.teams.clone(),
+                },
+            ).unwrap();
+        });
+    }
+
+    end_events.iter().for_each(|e| {
+        server.endpoint().broadcast_message_on(
+            ChannelId::UnorderedReliable,
+            ServerMessage::MatchEnd { outcome: MatchOutcome { winner: e.winner.clone() } },
+        ).unwrap();
+    });
+}
+
+pub fn update_flag_feed(
+    mut flag_events: EventReader<FlagEvent>,
+    server: Res<Server>,
+) {
+    flag_events.iter().for_each(|e| {
+        server.endpoint().broadcast_message_on(
+            ChannelId::UnorderedReliable,
+            ServerMessage::FlagFeed { event: e.clone() },
+        ).unwrap();
+    });
+}
+
+pub fn update_map(
+    mut init_events: EventReader<OnInitEvent>,
+    map: Option<Res<MapSource>>,
+    server: Res<Server>,
+) {
+    let Some(map) = map else { return; };
+    init_events.iter().for_each(|_| {
+        server.endpoint().broadcast_message_on(
+            ChannelId::UnorderedReliable,
+            ServerMessage::MapInit { source: map.clone() },
+        ).unwrap();
+    });
+}
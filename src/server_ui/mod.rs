@@ -21,7 +21,7 @@ impl Plugin for ServerUIPlugin {
     fn build(&self, app: &mut App) {
         app
             .add_system(in_game_on_load.in_set(OnUpdate(AppState::Loading)))
-            .add_system(server_start_listening.in_schedule(OnEnter(AppState::InGame)))
+            .add_system(server_start_listening.in_schedule(OnExit(AppState::Loading)))
             .add_system(server_stats_egui.run_if(is_server_listening));
     }
 }
@@ -24,8 +24,19 @@ impl Plugin for ClientSimulationPlugin {
                     on_player_update,
                     on_health_update,
                     on_timer_update,
+                    on_map_init,
+                    on_zone_update,
+                    on_control_points_update,
+                    on_round_update,
+                    on_wave_update,
+                    on_game_mode.before(on_match_end),
+                    on_match_end,
                 ).in_set(ClientUpdate).before(on_object_despawn)
             )
+            .add_system(on_bullet_bounce.in_set(ClientUpdate).after(phys_obj_updater).before(on_object_despawn))
+            .add_system(on_weapon_status.in_set(ClientUpdate).before(on_object_despawn))
+            .add_system(on_skill_status.in_set(ClientUpdate).before(on_object_despawn))
+            .add_system(on_shields_update.in_set(ClientUpdate).before(on_object_despawn))
             .add_system(on_object_despawn.in_set(ClientUpdate));
     }
 }
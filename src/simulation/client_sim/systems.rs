@@ -1,15 +1,23 @@
 use crate::asset_loader::components::SpriteEnum;
 use crate::simulation::{Lobby, Object, PlayerData};
 use crate::simulation::SyncedObjects;
-use bevy::prelude::{Children, Commands, default, Entity, EventReader, EventWriter, Query, Res, ResMut, SpriteBundle, Transform, With};
+use bevy::prelude::{Children, Commands, default, Entity, EventReader, EventWriter, Query, Res, ResMut, SpriteBundle, Transform, With, Quat, Vec3};
 use bevy_rapier2d::dynamics::Velocity;
-use bevy::log::info;
+use bevy::log::{info, warn};
 use bevy::hierarchy::BuildChildren;
 use crate::asset_loader::resources::SpriteAssets;
-use crate::client_networking::{ClientId, RecvHealthUpdateEvent, RecvMatchTimeEvent, RecvObjectDespawnEvent, RecvPhysObjUpdateEvent, RecvPlayerConnectEvent, RecvPlayerDataUpdateEvent, RecvPlayerLeaveEvent, RecvPlayerSpawnEvent, RecvTurretUpdateEvent, RecvYouConnectEvent};
+use crate::client_networking::{ClientId, RecvHealthUpdateEvent, RecvMatchTimeEvent, RecvObjectDespawnEvent, RecvPhysObjUpdateEvent, RecvPlayerConnectEvent, RecvPlayerDataUpdateEvent, RecvPlayerLeaveEvent, RecvPlayerSpawnEvent, RecvTurretUpdateEvent, RecvYouConnectEvent, RecvMapInitEvent, RecvZoneUpdateEvent, RecvGameModeEvent, RecvMatchEndEvent, RecvControlPointsEvent, RecvRoundEvent, RecvWaveEvent, RecvBulletBounceEvent, RecvWeaponStatusEvent, RecvSkillStatusEvent, RecvShieldsEvent};
 use crate::simulation::client_sim::PlayerSpawnBuffer;
+use crate::simulation::map::{MapEntity, MapSource, spawn_map};
 use crate::simulation::events::OnPlayerSpawnEvent;
-use crate::simulation::server_sim::match_ffa::MatchTimer;
+use crate::simulation::server_sim::game_mode::{ActiveGameMode, GameModeRegistry, MatchOutcome, MatchTimer};
+use crate::simulation::server_sim::game_mode::battle_royale::BattleRoyaleZone;
+use crate::simulation::server_sim::game_mode::king_of_the_hill::ControlPoints;
+use crate::simulation::server_sim::game_mode::elimination::RoundState;
+use crate::simulation::server_sim::game_mode::survival::WaveState;
+use crate::simulation::server_sim::ai::is_ai_player;
+use crate::simulation::server_sim::weapon::{DEFAULT_WEAPON, WeaponStatus};
+use crate::simulation::server_sim::skill::{ActiveShields, SkillStatus};
 use crate::simulation::server_sim::player::{Health, Player, PlayerTurret};
 use crate::utils::commands::despawn::CustomDespawnExt;
 use crate::utils::prefabs::{get_player_bundle, get_turret_bundle};
@@ -137,13 +145,16 @@ pub fn on_player_spawn(
         commands.entity(*entity).insert(get_player_bundle(e.player_id, Some(e.position)))
             .insert(Object { id: e.object_id })
             .with_children(|p| {
-                p.spawn(get_turret_bundle());
+                p.spawn(get_turret_bundle(DEFAULT_WEAPON));
             });
 
-        if let Some(mut data) = lobby.player_data.get_mut(&e.player_id) {
-            data.object_id = Some(e.object_id);
-        } else {
-            lobby.player_data.insert(e.player_id, PlayerData::new(e.object_id));
+        // AI tanks aren't part of the lobby
+        if !is_ai_player(e.player_id) {
+            if let Some(mut data) = lobby.player_data.get_mut(&e.player_id) {
+                data.object_id = Some(e.object_id);
+            } else {
+                lobby.player_data.insert(e.player_id, PlayerData::new(e.object_id));
+            }
         }
 
         spawn_writer.send(OnPlayerSpawnEvent {
@@ -161,7 +172,7 @@ pub fn on_player_update(
     mut lobby: ResMut<Lobby>,
 ) {
     player_update_events.iter().for_each(|e| {
-        *lobby.player_data.get_mut(&e.id).unwrap() = e.data.clone();
+        lobby.player_data.insert(e.id, e.data.clone());
     });
 }
 
@@ -209,3 +220,129 @@ pub fn on_object_despawn(
         }
     });
 }
+
+pub fn on_map_init(
+    mut events: EventReader<RecvMapInitEvent>,
+    map_entities: Query<Entity, With<MapEntity>>,
+    current: Option<Res<MapSource>>,
+    mut commands: Commands,
+) {
+    let Some(e) = events.iter().last() else { return; };
+    if let Some(current) = current && *current == e.source { return; }
+
+    map_entities.iter().for_each(|entity| commands.entity(entity).custom_despawn());
+
+    let map = e.source.build();
+    spawn_map(&mut commands, &map);
+    commands.insert_resource(e.source.clone());
+    commands.insert_resource(map);
+}
+
+pub fn on_zone_update(
+    mut events: EventReader<RecvZoneUpdateEvent>,
+    mut commands: Commands,
+) {
+    if let Some(e) = events.iter().last() {
+        commands.insert_resource::<BattleRoyaleZone>(e.zone.clone());
+    }
+}
+
+pub fn on_control_points_update(
+    mut events: EventReader<RecvControlPointsEvent>,
+    mut commands: Commands,
+) {
+    if let Some(e) = events.iter().last() {
+        commands.insert_resource::<ControlPoints>(e.control_points.clone());
+    }
+}
+
+pub fn on_round_update(
+    mut events: EventReader<RecvRoundEvent>,
+    mut commands: Commands,
+) {
+    if let Some(e) = events.iter().last() {
+        commands.insert_resource::<RoundState>(e.rounds.clone());
+    }
+}
+
+pub fn on_wave_update(
+    mut events: EventReader<RecvWaveEvent>,
+    mut commands: Commands,
+) {
+    if let Some(e) = events.iter().last() {
+        commands.insert_resource::<WaveState>(e.waves.clone());
+    }
+}
+
+/// Bounces arrive reliably, so bullets still turn around when the physics update is lost.
+pub fn on_bullet_bounce(
+    mut events: EventReader<RecvBulletBounceEvent>,
+    objects: Res<SyncedObjects>,
+    mut query: Query<(&mut Transform, Option<&mut Velocity>), With<Object>>,
+) {
+    events.iter().for_each(|e| {
+        let Some(&entity) = objects.objects.get(&e.object_id) else { return; };
+        let Ok((mut trans, vel)) = query.get_mut(entity) else { return; };
+
+        trans.translation = e.position.extend(trans.translation.z);
+        trans.rotation = Quat::from_axis_angle(Vec3::Z, e.velocity.y.atan2(e.velocity.x));
+        if let Some(mut vel) = vel {
+            vel.linvel = e.velocity;
+        }
+    });
+}
+
+pub fn on_weapon_status(
+    mut events: EventReader<RecvWeaponStatusEvent>,
+    mut commands: Commands,
+) {
+    if let Some(e) = events.iter().last() {
+        commands.insert_resource::<WeaponStatus>(e.status.clone());
+    }
+}
+
+pub fn on_skill_status(
+    mut events: EventReader<RecvSkillStatusEvent>,
+    mut commands: Commands,
+) {
+    if let Some(e) = events.iter().last() {
+        commands.insert_resource::<SkillStatus>(e.status.clone());
+    }
+}
+
+pub fn on_shields_update(
+    mut events: EventReader<RecvShieldsEvent>,
+    mut commands: Commands,
+) {
+    if let Some(e) = events.iter().last() {
+        commands.insert_resource::<ActiveShields>(e.shields.clone());
+    }
+}
+
+/// The mode is announced at the start of every match, which also clears the last match's outcome.
+pub fn on_game_mode(
+    mut events: EventReader<RecvGameModeEvent>,
+    registry: Res<GameModeRegistry>,
+    mut commands: Commands,
+) {
+    let Some(e) = events.iter().last() else { return; };
+    let Some(rules) = registry.get(&e.name) else {
+        warn!("Server is playing unknown mode '{}'", e.name);
+        return;
+    };
+
+    commands.insert_resource(ActiveGameMode {
+        teams: e.teams.clone(),
+        ..ActiveGameMode::new(rules.clone())
+    });
+    commands.remove_resource::<MatchOutcome>();
+}
+
+pub fn on_match_end(
+    mut events: EventReader<RecvMatchEndEvent>,
+    mut commands: Commands,
+) {
+    if let Some(e) = events.iter().last() {
+        commands.insert_resource(e.outcome.clone());
+    }
+}
@@ -4,6 +4,8 @@ use crate::simulation::ObjectId;
 
 pub struct OnObjectDespawnEvent {
     pub id: ObjectId,
+    /// Who dropped the mine, if it was one, mines are only sent to their owner's team.
+    pub mine_owner: Option<PlayerId>,
 }
 
 pub struct OnPlayerConnectEvent {
@@ -16,6 +18,11 @@ pub struct OnPlayerSpawnEvent {
     pub position: Vec2,
 }
 
+/// Sent when something in a player's `PlayerData` changed that clients should hear about.
+pub struct OnPlayerDataChangedEvent {
+    pub player_id: PlayerId,
+}
+
 pub struct OnRespawnTimerFinish {
     pub player_id: PlayerId,
 }
@@ -0,0 +1,167 @@
+use bevy::math::Vec2;
+use crate::simulation::map::{Boundary, MapData, Obstacle};
+use crate::simulation::server_sim::pickup::{BuffEffect, PickupKind, PickupSpawner};
+use crate::utils::generate_evenly_spaced_points_on_polygon_edges;
+
+const SPAWN_POINTS: [[f32; 2]; 8] = [
+    [846., 537.],
+    [840., 569.],
+    [851., 593.],
+    [880., 601.],
+    [901., 589.],
+    [907., 569.],
+    [907., 539.],
+    [876., 523.],
+];
+
+const TREE_RING: [[f32; 2]; 13] = [
+    [886., 296.],
+    [1029., 315.],
+    [1088., 374.],
+    [1120., 510.],
+    [1045., 626.],
+    [964., 694.],
+    [914., 749.],
+    [783., 734.],
+    [698., 678.],
+    [647., 598.],
+    [651., 499.],
+    [657., 365.],
+    [758., 327.],
+];
+
+const HEALTH_PACKS: [[f32; 2]; 2] = [
+    [715., 560.],
+    [1075., 470.],
+];
+
+const BUFF_SPAWNERS: [[f32; 2]; 2] = [
+    [880., 360.],
+    [880., 690.],
+];
+
+const SKILL_SPAWNERS: [([f32; 2], &str); 2] = [
+    ([760., 690.], "repair"),
+    ([1000., 380.], "ricochet_rounds"),
+];
+
+fn convert_point(point: [f32; 2]) -> [f32; 2] {
+    [(point[0] - 875.) * 20., (point[1] - 565.) * 20.]
+}
+
+pub fn default_map() -> MapData {
+    let tree_bounds = [
+        vec![
+            [833., 506.],
+            [886., 499.],
+            [896., 481.],
+            [910., 465.],
+            [924., 448.],
+            [896., 431.],
+            [862., 425.],
+            [830., 431.],
+            [799., 447.],
+        ],
+        vec![
+            [797., 483.],
+            [828., 519.],
+            [824., 529.],
+            [821., 551.],
+            [830., 576.],
+            [770., 559.],
+            [764., 522.],
+            [773., 499.],
+        ],
+        vec![
+            [797., 583.],
+            [830., 591.],
+            [835., 601.],
+            [845., 610.],
+            [862., 613.],
+            [862., 638.],
+            [836., 638.],
+            [810., 628.],
+            [801., 598.],
+        ],
+        vec![
+            [879., 613.],
+            [897., 613.],
+            [916., 606.],
+            [924., 595.],
+            [934., 595.],
+            [955., 615.],
+            [946., 624.],
+            [934., 638.],
+            [902., 643.],
+            [879., 640.],
+        ],
+        vec![
+            [900., 506.],
+            [914., 483.],
+            [929., 468.],
+            [940., 459.],
+            [953., 456.],
+            [978., 475.],
+            [989., 496.],
+            [991., 510.],
+            [982., 526.],
+            [973., 529.],
+            [955., 538.],
+            [958., 559.],
+            [980., 568.],
+            [999., 565.],
+            [1004., 547.],
+            [991., 530.],
+            [1001., 516.],
+            [1032., 532.],
+            [1039., 564.],
+            [1032., 586.],
+            [1010., 593.],
+            [982., 576.],
+            [972., 589.],
+            [964., 601.],
+            [941., 583.],
+            [927., 578.],
+            [929., 568.],
+            [929., 541.],
+            [917., 522.],
+        ],
+    ];
+
+    let obstacles = tree_bounds.into_iter().map(|points| Obstacle {
+        polygon: points.into_iter().map(|p| Vec2::from(convert_point(p))).collect(),
+        tree_spacing: 200.,
+    }).collect();
+
+    let trees = generate_evenly_spaced_points_on_polygon_edges(&TREE_RING, 10.)
+        .into_iter().map(|p| Vec2::from(convert_point(p.to_array())))
+        .collect();
+
+    let health_packs = HEALTH_PACKS.into_iter().map(|p| PickupSpawner {
+        position: Vec2::from(convert_point(p)),
+        kind: PickupKind::Health { amount: 50. },
+        respawn_time: 20.,
+    });
+    let buffs = BUFF_SPAWNERS.into_iter().zip([
+        BuffEffect::Speed { multiplier: 1.3 },
+        BuffEffect::Damage { multiplier: 1.3 },
+    ]).map(|(p, effect)| PickupSpawner {
+        position: Vec2::from(convert_point(p)),
+        kind: PickupKind::Buff { effect, duration: 10. },
+        respawn_time: 30.,
+    });
+    let skills = SKILL_SPAWNERS.into_iter().map(|(p, name)| PickupSpawner {
+        position: Vec2::from(convert_point(p)),
+        kind: PickupKind::Skill { name: name.to_owned() },
+        respawn_time: 60.,
+    });
+
+    MapData {
+        bounds: TREE_RING.into_iter().map(|p| Vec2::from(convert_point(p))).collect(),
+        spawn_points: SPAWN_POINTS.into_iter().map(|p| Vec2::from(convert_point(p))).collect(),
+        trees,
+        obstacles,
+        boundary: Boundary::Walls,
+        pickup_spawners: health_packs.chain(buffs).chain(skills).collect(),
+    }
+}
@@ -0,0 +1,149 @@
+use std::f32::consts::TAU;
+use std::ops::Range;
+use bevy::math::Vec2;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use crate::simulation::map::{Boundary, MapData};
+use crate::utils::{filter_points_by_min_distance, generate_evenly_spaced_points_on_polygon_edges, generate_random_points_in_polygon, is_point_in_polygon, nudge_points_randomly};
+
+pub struct MapGenConfig {
+    pub arena_radius: f32,
+    pub boundary_vertices: usize,
+    pub boundary_jitter: f32,
+    pub boundary_tree_spacing: f32,
+    pub forest_count: usize,
+    pub forest_radius: Range<f32>,
+    pub forest_vertices: usize,
+    pub trees_per_forest: usize,
+    pub tree_nudge: f32,
+    pub tree_min_distance: f32,
+    pub clearing_count: usize,
+    pub clearing_radius: f32,
+    pub spawn_count: usize,
+    pub spawn_ring_radius: f32,
+    pub spawn_clearing_radius: f32,
+    pub corridor_width: f32,
+}
+
+impl Default for MapGenConfig {
+    fn default() -> Self {
+        MapGenConfig {
+            arena_radius: 4500.,
+            boundary_vertices: 16,
+            boundary_jitter: 0.15,
+            boundary_tree_spacing: 200.,
+            forest_count: 9,
+            forest_radius: 500.0..1100.0,
+            forest_vertices: 8,
+            trees_per_forest: 30,
+            tree_nudge: 40.,
+            tree_min_distance: 180.,
+            clearing_count: 3,
+            clearing_radius: 700.,
+            spawn_count: 8,
+            spawn_ring_radius: 1500.,
+            spawn_clearing_radius: 450.,
+            corridor_width: 400.,
+        }
+    }
+}
+
+/// Builds a bounded arena centered on the origin. The same seed and config always produce the
+/// same map, which is what lets clients rebuild it from the seed alone.
+pub fn generate_map(seed: u64, config: &MapGenConfig) -> MapData {
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
+
+    let bounds = random_polygon(&mut rng, Vec2::ZERO, config.arena_radius,
+                                config.boundary_vertices, config.boundary_jitter);
+
+    // Spawns sit evenly around a ring so no spawn is favoured over another
+    let offset = rng.gen_range(0.0..TAU);
+    let spawn_points = (0..config.spawn_count).map(|i| {
+        let angle = offset + TAU * i as f32 / config.spawn_count as f32;
+        let jitter: f32 = rng.gen_range(0.85..1.15);
+        Vec2::from_angle(angle) * config.spawn_ring_radius * jitter
+    }).collect::<Vec<Vec2>>();
+
+    let clearings = (0..config.clearing_count)
+        .map(|_| random_point_in_disk(&mut rng, config.arena_radius * 0.7))
+        .chain([Vec2::ZERO])
+        .collect::<Vec<Vec2>>();
+
+    let mut forest_trees = Vec::new();
+    for _ in 0..config.forest_count {
+        let center = random_point_in_disk(&mut rng, config.arena_radius * 0.85);
+        let radius = rng.gen_range(config.forest_radius.clone());
+        let polygon = random_polygon(&mut rng, center, radius,
+                                     config.forest_vertices, config.boundary_jitter * 2.);
+        let points = generate_random_points_in_polygon(&polygon, config.trees_per_forest, rng.gen());
+        forest_trees.extend(nudge_points_randomly(points, config.tree_nudge, rng.gen()));
+    }
+
+    // Every spawn gets a corridor to the central clearing, so they can all reach each other
+    let is_clear = |point: &Vec2| {
+        is_point_in_polygon(point, &bounds)
+            && clearings.iter().all(|c| c.distance(*point) > config.clearing_radius)
+            && spawn_points.iter().all(|&spawn| {
+            spawn.distance(*point) > config.spawn_clearing_radius
+                && distance_to_segment(*point, spawn, Vec2::ZERO) > config.corridor_width / 2.
+        })
+    };
+
+    let trees = filter_points_by_min_distance(
+        generate_evenly_spaced_points_on_polygon_edges(&bounds, config.boundary_tree_spacing)
+            .into_iter()
+            .chain(forest_trees.into_iter().filter(is_clear))
+            .collect(),
+        config.tree_min_distance,
+    );
+
+    MapData {
+        bounds,
+        spawn_points,
+        trees,
+        obstacles: Vec::new(),
+        boundary: Boundary::Walls,
+        pickup_spawners: Vec::new(),
+    }
+}
+
+fn random_polygon(rng: &mut ChaCha8Rng, center: Vec2, radius: f32, vertices: usize, jitter: f32) -> Vec<Vec2> {
+    (0..vertices).map(|i| {
+        let angle = TAU * i as f32 / vertices as f32;
+        center + Vec2::from_angle(angle) * radius * rng.gen_range((1. - jitter)..=(1. + jitter))
+    }).collect()
+}
+
+fn random_point_in_disk(rng: &mut ChaCha8Rng, radius: f32) -> Vec2 {
+    Vec2::from_angle(rng.gen_range(0.0..TAU)) * radius * rng.gen::<f32>().sqrt()
+}
+
+fn distance_to_segment(point: Vec2, start: Vec2, end: Vec2) -> f32 {
+    let segment = end - start;
+    let t = ((point - start).dot(segment) / segment.length_squared().max(f32::EPSILON)).clamp(0., 1.);
+    point.distance(start + segment * t)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::simulation::map::MAP_CLEARANCE;
+
+    #[test]
+    fn every_spawn_point_is_reachable() {
+        let config = MapGenConfig::default();
+        (0..20).for_each(|seed| {
+            let map = generate_map(seed, &config);
+            let unreachable = map.unreachable_spawn_points(MAP_CLEARANCE);
+            assert!(unreachable.is_empty(), "seed {seed} has unreachable spawns: {unreachable:?}");
+        });
+    }
+
+    #[test]
+    fn same_seed_gives_same_map() {
+        let config = MapGenConfig::default();
+        (0..5).for_each(|seed| {
+            assert_eq!(generate_map(seed, &config), generate_map(seed, &config), "seed {seed}");
+        });
+    }
+}
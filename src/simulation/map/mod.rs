@@ -0,0 +1,196 @@
+mod default_map;
+pub mod generator;
+
+pub use default_map::default_map;
+
+use std::fs;
+use std::path::Path;
+use bevy::log::warn;
+use bevy::math::{IVec2, Vec2};
+use bevy::prelude::{Commands, Component, Resource, Transform};
+use serde::{Deserialize, Serialize};
+use crate::simulation::map::generator::{generate_map, MapGenConfig};
+use crate::utils::{generate_evenly_spaced_points_within_polygon, is_point_in_polygon, nudge_points_randomly};
+use crate::simulation::server_sim::pickup::PickupSpawner;
+use crate::utils::prefabs::{arena_walls, pickup_spawner, spawn_point, tree, TANK_COLLIDER_RADIUS, TREE_COLLIDER_RADIUS};
+
+/// Distance a tank's center has to keep from a tree's center to fit past it.
+pub const MAP_CLEARANCE: f32 = TREE_COLLIDER_RADIUS + TANK_COLLIDER_RADIUS;
+
+/// Describes which map to build. This is what gets replicated to clients, so every peer
+/// rebuilds the same world locally instead of having it streamed. Only maps loaded from
+/// files are sent in full, since clients can't be expected to have them.
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize, Default)]
+pub enum MapSource {
+    #[default]
+    Default,
+    Procedural { seed: u64 },
+    Custom(MapData),
+}
+
+impl MapSource {
+    pub fn build(&self) -> MapData {
+        match self {
+            MapSource::Default => default_map(),
+            MapSource::Procedural { seed } => {
+                let map = generate_map(*seed, &MapGenConfig::default());
+                let unreachable = map.unreachable_spawn_points(MAP_CLEARANCE);
+                if !unreachable.is_empty() {
+                    warn!("Map generated with seed {} has unreachable spawn points: {:?}", seed, unreachable);
+                }
+                map
+            }
+            MapSource::Custom(map) => map.clone(),
+        }
+    }
+}
+
+#[derive(Resource, Debug, Clone, PartialEq, Default, Serialize, Deserialize)]
+pub struct MapData {
+    pub bounds: Vec<Vec2>,
+    pub spawn_points: Vec<Vec2>,
+    pub trees: Vec<Vec2>,
+    #[serde(default)]
+    pub obstacles: Vec<Obstacle>,
+    #[serde(default)]
+    pub boundary: Boundary,
+    #[serde(default)]
+    pub pickup_spawners: Vec<PickupSpawner>,
+}
+
+/// What happens at the edge of `MapData::bounds`.
+#[derive(Debug, Clone, Copy, PartialEq, Default, Serialize, Deserialize)]
+pub enum Boundary {
+    #[default]
+    Walls,
+    DamageZone { damage_per_second: f32 },
+}
+
+/// A polygon filled with trees, `tree_spacing` apart.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Obstacle {
+    pub polygon: Vec<Vec2>,
+    pub tree_spacing: f32,
+}
+
+impl Obstacle {
+    pub fn tree_positions(&self) -> Vec<Vec2> {
+        let points = generate_evenly_spaced_points_within_polygon(
+            self.polygon.as_slice(),
+            self.tree_spacing,
+        );
+        nudge_points_randomly(points, self.tree_spacing / 20., 0)
+    }
+}
+
+impl MapData {
+    /// Reads a map saved with `save`, rejecting it if its bounds aren't a polygon.
+    pub fn load(path: impl AsRef<Path>) -> Result<MapData, String> {
+        let contents = fs::read_to_string(path).map_err(|e| e.to_string())?;
+        let map: MapData = ron::from_str(&contents).map_err(|e| e.to_string())?;
+        if map.bounds.len() < 3 {
+            return Err(format!("Map bounds need at least 3 points, found {}", map.bounds.len()));
+        }
+        Ok(map)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), String> {
+        let contents = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())
+            .map_err(|e| e.to_string())?;
+        if let Some(parent) = path.as_ref().parent() {
+            fs::create_dir_all(parent).map_err(|e| e.to_string())?;
+        }
+        fs::write(path, contents).map_err(|e| e.to_string())
+    }
+
+    pub fn contains(&self, point: Vec2) -> bool {
+        is_point_in_polygon(&point, &self.bounds)
+    }
+
+    pub fn bounding_box(&self) -> [Vec2; 2] {
+        let min = self.bounds.iter().fold(Vec2::splat(f32::MAX), |acc, &p| acc.min(p));
+        let max = self.bounds.iter().fold(Vec2::splat(f32::MIN), |acc, &p| acc.max(p));
+        [min, max]
+    }
+
+    /// Every tree on the map, including the ones filling obstacles.
+    pub fn tree_positions(&self) -> Vec<Vec2> {
+        self.trees.iter().copied()
+            .chain(self.obstacles.iter().flat_map(Obstacle::tree_positions))
+            .collect()
+    }
+
+    /// Flood fills the walkable area from the first spawn point and returns every spawn point
+    /// that couldn't be reached by a body needing `clearance` distance from tree centers.
+    pub fn unreachable_spawn_points(&self, clearance: f32) -> Vec<Vec2> {
+        const CELL_SIZE: f32 = 50.;
+
+        let Some(&start) = self.spawn_points.first() else { return Vec::new(); };
+        // Nothing is inside bounds that aren't a polygon
+        if self.bounds.len() < 3 { return self.spawn_points.clone(); }
+
+        let [min, max] = self.bounding_box();
+        let size = ((max - min) / CELL_SIZE).ceil().as_ivec2() + IVec2::ONE;
+
+        let to_cell = |p: Vec2| ((p - min) / CELL_SIZE).floor().as_ivec2();
+        let to_world = |cell: IVec2| min + (cell.as_vec2() + 0.5) * CELL_SIZE;
+        let in_grid = |cell: IVec2| cell.x >= 0 && cell.y >= 0 && cell.x < size.x && cell.y < size.y;
+        let index = |cell: IVec2| (cell.y * size.x + cell.x) as usize;
+
+        let mut blocked = vec![false; (size.x * size.y) as usize];
+        for y in 0..size.y {
+            for x in 0..size.x {
+                let cell = IVec2::new(x, y);
+                blocked[index(cell)] = !is_point_in_polygon(&to_world(cell), &self.bounds);
+            }
+        }
+
+        let reach = (clearance / CELL_SIZE).ceil() as i32;
+        self.tree_positions().into_iter().for_each(|tree| {
+            let center = to_cell(tree);
+            for dy in -reach..=reach {
+                for dx in -reach..=reach {
+                    let cell = center + IVec2::new(dx, dy);
+                    if in_grid(cell) && to_world(cell).distance(tree) < clearance {
+                        blocked[index(cell)] = true;
+                    }
+                }
+            }
+        });
+
+        let mut visited = vec![false; blocked.len()];
+        let mut frontier = vec![to_cell(start)];
+        while let Some(cell) = frontier.pop() {
+            if !in_grid(cell) || visited[index(cell)] || blocked[index(cell)] { continue; }
+            visited[index(cell)] = true;
+            frontier.extend([IVec2::X, IVec2::NEG_X, IVec2::Y, IVec2::NEG_Y].map(|dir| cell + dir));
+        }
+
+        self.spawn_points.iter().copied().filter(|&point| {
+            let cell = to_cell(point);
+            !in_grid(cell) || !visited[index(cell)]
+        }).collect()
+    }
+}
+
+#[derive(Component)]
+pub struct MapEntity;
+
+pub fn spawn_map(commands: &mut Commands, map: &MapData) {
+    if map.boundary == Boundary::Walls && map.bounds.len() >= 3 {
+        commands.spawn(arena_walls(&map.bounds)).insert(MapEntity);
+    }
+
+    map.spawn_points.iter().for_each(|&point| {
+        commands.spawn(spawn_point(point)).insert(MapEntity);
+    });
+
+    map.tree_positions().into_iter().for_each(|p| {
+        commands.spawn(tree())
+            .insert((Transform::from_xyz(p.x, p.y, 0.), MapEntity));
+    });
+
+    map.pickup_spawners.iter().for_each(|spawner| {
+        commands.spawn(pickup_spawner(spawner.clone())).insert(MapEntity);
+    });
+}
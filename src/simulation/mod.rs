@@ -7,11 +7,15 @@ use serde::{Deserialize, Serialize};
 use crate::simulation::events::*;
 use crate::simulation::client_sim::ClientSimulationPlugin;
 use crate::simulation::server_sim::ServerSimulationPlugin;
+use crate::simulation::server_sim::leveling::Progression;
+use crate::simulation::team::{Team, TeamPlugin};
 use crate::utils::networking::messages::PlayerId;
 
 pub mod events;
 pub mod client_sim;
 pub mod server_sim;
+pub mod map;
+pub mod team;
 
 pub struct SimulationPlugin;
 
@@ -19,7 +23,8 @@ impl Plugin for SimulationPlugin {
     fn build(&self, app: &mut App) {
         app
             .add_plugin(ServerSimulationPlugin)
-            .add_plugin(ClientSimulationPlugin);
+            .add_plugin(ClientSimulationPlugin)
+            .add_plugin(TeamPlugin);
 
         app
             .insert_resource(Lobby::default())
@@ -28,7 +33,8 @@ impl Plugin for SimulationPlugin {
         app
             .add_event::<OnObjectDespawnEvent>()
             .add_event::<OnPlayerSpawnEvent>()
-            .add_event::<OnPlayerConnectEvent>();
+            .add_event::<OnPlayerConnectEvent>()
+            .add_event::<OnPlayerDataChangedEvent>();
 
     }
 }
@@ -81,6 +87,17 @@ impl Lobby {
             Ok(())
         }
     }
+
+    pub fn team_of(&self, player_id: PlayerId) -> Option<Team> {
+        self.player_data.get(&player_id).and_then(|data| data.team)
+    }
+
+    pub fn are_teammates(&self, player: PlayerId, other: PlayerId) -> bool {
+        match (self.team_of(player), self.team_of(other)) {
+            (Some(team), Some(other_team)) => team == other_team,
+            _ => false,
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
@@ -88,6 +105,17 @@ pub struct PlayerData {
     pub object_id: Option<ObjectId>,
     pub kills: u32,
     pub deaths: u32,
+    pub assists: u32,
+    /// Damage dealt to other players' tanks. Only sent to clients along with other changes to
+    /// the player's data and when the match ends, rather than on every hit.
+    pub damage_dealt: f32,
+    pub team: Option<Team>,
+    pub captures: u32,
+    /// Seconds spent on a control point held by your team.
+    pub hill_time: f32,
+    pub progression: Progression,
+    /// The weapon picked for the next spawn, `DEFAULT_WEAPON` if there isn't one.
+    pub weapon: Option<String>,
 }
 
 impl PlayerData {
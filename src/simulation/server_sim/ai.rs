@@ -0,0 +1,163 @@
+use std::sync::atomic::{AtomicU64, Ordering};
+use bevy::app::App;
+use bevy::prelude::*;
+use bevy_rapier2d::geometry::{CollisionGroups, Group};
+use crate::asset_loader::components::SpriteEnum;
+use crate::ServerSet::ServerUpdate;
+use crate::simulation::map::{MAP_CLEARANCE, MapData};
+use crate::simulation::server_sim::player::{Player, PlayerInput, PlayerTurret};
+use crate::utils::distance_to_polygon_edges;
+use crate::utils::networking::messages::PlayerId;
+use crate::utils::prefabs::MAP_COLLISION_GROUP;
+
+/// Server controlled tanks. They are regular `Player` entities driven through `PlayerInput`,
+/// so moving, aiming, shooting and taking damage work the same as for everyone else.
+pub struct AiPlugin;
+
+impl Plugin for AiPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(
+                (
+                    enter_arena,
+                    drive_ai_tanks,
+                ).chain().in_set(ServerUpdate)
+            )
+            .add_system(paint_ai_tanks);
+    }
+}
+
+/// AI tanks take ids from here up, so they never clash with connected players.
+/// They are never added to the `Lobby`.
+pub const AI_PLAYER_ID_START: PlayerId = 1 << 48;
+
+const AI_TANK_SPRITE: SpriteEnum = SpriteEnum::TankClassic;
+const AI_TURRET_SPRITE: SpriteEnum = SpriteEnum::TankClassicTurret;
+/// Distance AI tanks try to keep from their target while circling it.
+const ENGAGE_RANGE: f32 = 500.;
+const FIRE_RANGE: f32 = 900.;
+const STRAFE_SWITCH_TIME: f32 = 3.;
+
+static AI_COUNTER: AtomicU64 = AtomicU64::new(AI_PLAYER_ID_START);
+
+pub fn next_ai_player_id() -> PlayerId {
+    AI_COUNTER.fetch_add(1, Ordering::Relaxed)
+}
+
+pub fn is_ai_player(player_id: PlayerId) -> bool {
+    player_id >= AI_PLAYER_ID_START
+}
+
+#[derive(Component)]
+pub struct AiTank {
+    /// Seconds between shots.
+    pub fire_interval: f32,
+    pub fire_cooldown: f32,
+    pub strafe_direction: f32,
+    pub strafe_time: f32,
+}
+
+impl AiTank {
+    pub fn new(fire_interval: f32) -> Self {
+        AiTank {
+            fire_interval,
+            fire_cooldown: fire_interval,
+            strafe_direction: 1.,
+            strafe_time: STRAFE_SWITCH_TIME,
+        }
+    }
+}
+
+/// The tank spawned outside the map and passes through trees and walls until it's inside.
+#[derive(Component)]
+pub struct EnteringArena;
+
+pub fn entering_collision_groups() -> CollisionGroups {
+    CollisionGroups::new(Group::ALL, Group::ALL - MAP_COLLISION_GROUP)
+}
+
+fn enter_arena(
+    mut ai_q: Query<(Entity, &Transform, &mut CollisionGroups), (With<AiTank>, With<EnteringArena>)>,
+    map: Option<Res<MapData>>,
+    mut commands: Commands,
+) {
+    let Some(map) = map else { return; };
+    ai_q.iter_mut().for_each(|(entity, trans, mut groups)| {
+        let position = trans.translation.truncate();
+        if !map.contains(position) || distance_to_polygon_edges(position, &map.bounds) < MAP_CLEARANCE {
+            return;
+        }
+        *groups = CollisionGroups::default();
+        commands.entity(entity).remove::<EnteringArena>();
+    });
+}
+
+/// Drives at the nearest player until in range, then circles them while shooting.
+#[allow(clippy::type_complexity)]
+fn drive_ai_tanks(
+    mut ai_q: Query<(&mut AiTank, &mut PlayerInput, &Transform, Option<&EnteringArena>)>,
+    targets: Query<&Transform, (With<Player>, Without<AiTank>)>,
+    map: Option<Res<MapData>>,
+    time: Res<Time>,
+) {
+    let center = map.map(|map| {
+        let [min, max] = map.bounding_box();
+        (min + max) / 2.
+    }).unwrap_or_default();
+
+    ai_q.iter_mut().for_each(|(mut ai, mut input, trans, entering)| {
+        let position = trans.translation.truncate();
+        let target = targets.iter()
+            .map(|trans| trans.translation.truncate())
+            .min_by(|a, b| a.distance(position).total_cmp(&b.distance(position)));
+
+        ai.fire_cooldown -= time.delta_seconds();
+        ai.strafe_time -= time.delta_seconds();
+        if ai.strafe_time <= 0. {
+            ai.strafe_direction = -ai.strafe_direction;
+            ai.strafe_time = STRAFE_SWITCH_TIME;
+        }
+
+        input.fire_bullet = false;
+        let Some(target) = target else {
+            input.movement = if entering.is_some() { (center - position).normalize_or_zero() } else { Vec2::ZERO };
+            return;
+        };
+
+        let to_target = target - position;
+        input.movement = if entering.is_some() {
+            (center - position).normalize_or_zero()
+        } else if to_target.length() > ENGAGE_RANGE {
+            to_target.normalize_or_zero()
+        } else {
+            to_target.perp().normalize_or_zero() * ai.strafe_direction
+        };
+        input.mouse_pos = target;
+
+        if to_target.length() < FIRE_RANGE && ai.fire_cooldown <= 0. {
+            input.fire_bullet = true;
+            ai.fire_cooldown = ai.fire_interval;
+        }
+    });
+}
+
+/// Clients only know AI tanks by their id, so they are painted here on both sides.
+fn paint_ai_tanks(
+    mut tank_q: Query<(&Player, &mut SpriteEnum, Option<&Children>), Without<PlayerTurret>>,
+    mut turret_q: Query<&mut SpriteEnum, (With<PlayerTurret>, Without<Player>)>,
+) {
+    tank_q.iter_mut()
+        .filter(|(player, _, _)| is_ai_player(player.id))
+        .for_each(|(_, mut sprite, children)| {
+            if *sprite != AI_TANK_SPRITE {
+                *sprite = AI_TANK_SPRITE;
+            }
+
+            children.into_iter().flatten().for_each(|&child| {
+                let Ok(mut turret_sprite) = turret_q.get_mut(child) else { return; };
+                if *turret_sprite != AI_TURRET_SPRITE {
+                    *turret_sprite = AI_TURRET_SPRITE;
+                }
+            });
+        });
+}
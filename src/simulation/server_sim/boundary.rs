@@ -0,0 +1,50 @@
+use bevy::prelude::*;
+use crate::ServerSet::ServerUpdate;
+use crate::simulation::map::{Boundary, MapData};
+use crate::simulation::server_sim::ai::EnteringArena;
+use crate::simulation::server_sim::player::{OnDamageEvent, Player, PlayerSystemStage};
+
+/// Seconds between the hits a tank outside the map, or the battle royale zone, takes.
+pub const ZONE_DAMAGE_INTERVAL: f32 = 1.;
+
+pub struct BoundaryPlugin;
+
+impl Plugin for BoundaryPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(out_of_bounds_damage
+            .before(PlayerSystemStage::ApplyDamage)
+            .in_set(ServerUpdate));
+    }
+}
+
+/// Whole damage dealt every `ZONE_DAMAGE_INTERVAL` to deal `damage_per_second`, so health
+/// updates aren't sent every frame.
+pub fn zone_tick_damage(damage_per_second: f32) -> f32 {
+    (damage_per_second * ZONE_DAMAGE_INTERVAL).round().max(1.)
+}
+
+/// Walls are physical colliders spawned with the map, so only the damage zone needs a system.
+fn out_of_bounds_damage(
+    map: Option<Res<MapData>>,
+    players: Query<(Entity, &Transform), (With<Player>, Without<EnteringArena>)>,
+    mut damage_writer: EventWriter<OnDamageEvent>,
+    mut timer: Local<f32>,
+    time: Res<Time>,
+) {
+    let Some(map) = map else { return; };
+    let Boundary::DamageZone { damage_per_second } = map.boundary else { return; };
+
+    *timer -= time.delta_seconds();
+    if *timer > 0. { return; }
+    *timer += ZONE_DAMAGE_INTERVAL;
+
+    players.iter()
+        .filter(|(_, trans)| !map.contains(trans.translation.truncate()))
+        .for_each(|(victim, _)| {
+            damage_writer.send(OnDamageEvent {
+                victim,
+                attacker: None,
+                damage: zone_tick_damage(damage_per_second),
+            });
+        });
+}
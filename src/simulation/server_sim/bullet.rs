@@ -1,33 +1,44 @@
 use bevy::prelude::*;
 use bevy_rapier2d::geometry::Collider;
-use bevy_rapier2d::prelude::{ActiveEvents, CollisionEvent, RigidBody, Sensor, Velocity};
-use crate::asset_loader::components::SpriteEnum;
-use crate::simulation::Lobby;
+use bevy_rapier2d::prelude::{ActiveEvents, CollisionEvent, QueryFilter, RapierContext, RigidBody, Sensor, Velocity};
+use rand::Rng;
+use crate::asset_loader::resources::WeaponAssets;
 use crate::simulation::server_sim::player::components::PlayerInput;
+use crate::utils::networking::messages::PlayerId;
 use crate::display::sprite_updater::{AutoSorted, BULLET_LAYER};
 use crate::ServerSet::ServerUpdate;
-use crate::simulation::Object;
+use crate::simulation::{Lobby, Object, ObjectId};
+use crate::simulation::map::MapEntity;
 use crate::simulation::server_sim::player::components::{Player, PlayerTurret};
-use crate::simulation::server_sim::player::{OnPlayerDeathEvent, Health, OnKillEvent, OnHealthChangedEvent};
+use crate::simulation::server_sim::player::{OnDamageEvent, PlayerSystemStage};
 use crate::simulation::server_sim::bullet::BulletSystemStage::{CollisionHandle, CollisionSend};
+use crate::simulation::server_sim::skill::SkillLoadout;
+use crate::simulation::server_sim::spawn::SpawnProtection;
+use crate::simulation::server_sim::ai::AiTank;
+use crate::simulation::server_sim::explosion::ExplodeEvent;
+use crate::simulation::server_sim::hitscan::HitscanEvent;
+use crate::simulation::server_sim::homing::{Homing, steer_homing_bullets};
+use crate::simulation::server_sim::weapon::{ExplosionDefinition, WeaponDefinitions};
 use crate::utils::commands::despawn::CustomDespawnExt;
 
-static BULLET_COLLIDER_RADIUS: f32 = 10.;
 static BULLET_OFFSET: f32 = 95.;
-static BULLET_LIFETIME: f32 = 3.0;
-static BULLET_DAMAGE: f32 = 5.0;
-static BULLET_SCALE: f32 = 0.5;
 
 pub struct BulletPlugin;
 
 impl Plugin for BulletPlugin {
     fn build(&self, app: &mut App) {
         app.add_event::<BulletCollisionEvent>()
+            .add_event::<OnBulletBounceEvent>()
+            .add_event::<OnShieldBlockEvent>()
+            .add_event::<OnReloadEvent>()
             .configure_set(CollisionSend.before(CollisionHandle))
+            .configure_set(CollisionHandle.before(PlayerSystemStage::ApplyDamage))
             .add_systems(
                 (
                     fire_bullet.before(CollisionSend),
                     bullet_decay.after(fire_bullet),
+                    steer_homing_bullets.after(bullet_decay),
+                    bullet_obstacle_collision.after(steer_homing_bullets).before(CollisionHandle),
                     bullet_collision_sender.in_set(CollisionSend),
                     bullet_collision_handler.in_set(CollisionHandle)
                 ).in_set(ServerUpdate)
@@ -40,6 +51,27 @@ pub struct Bullet {
     pub owner: Option<Entity>,
     pub lifetime: f32,
     pub damage: f32,
+    pub radius: f32,
+    pub bounces_left: u32,
+    pub bounce_speed_loss: f32,
+    pub explosion: Option<ExplosionDefinition>,
+}
+
+/// Sent by a client pressing the reload key.
+pub struct OnReloadEvent {
+    pub player_id: PlayerId,
+}
+
+/// A shield stopped a hit, sent to clients so they can show it.
+pub struct OnShieldBlockEvent {
+    pub position: Vec2,
+}
+
+/// A bullet ricocheted, sent right away so clients don't wait on the next physics update.
+pub struct OnBulletBounceEvent {
+    pub object_id: ObjectId,
+    pub position: Vec2,
+    pub velocity: Vec2,
 }
 
 #[derive(Debug)]
@@ -55,57 +87,178 @@ pub enum BulletSystemStage {
 }
 
 
+/// Fires the equipped weapon of every turret whose tank is holding the trigger,
+/// as fast as the weapon's fire rate, magazine and heat allow. Hitscan weapons are resolved
+/// in `resolve_hitscan`. Bouncing bullet skills add to the weapon's bounces.
 fn fire_bullet(
     mut commands: Commands,
-    query: Query<(Entity, &PlayerInput, &Children), With<Player>>,
-    turret_query: Query<(&PlayerTurret, &GlobalTransform)>,
+    query: Query<(Entity, &Player, &PlayerInput, &Children, Option<&SkillLoadout>)>,
+    mut turret_query: Query<(&mut PlayerTurret, &GlobalTransform)>,
+    (weapon_assets, definitions): (Res<WeaponAssets>, Res<Assets<WeaponDefinitions>>),
+    mut reload_events: EventReader<OnReloadEvent>,
+    mut hitscan_writer: EventWriter<HitscanEvent>,
+    time: Res<Time>,
 ) {
-    query.iter().for_each(|(ent, input, children)| {
-        if !input.fire_bullet { return; }
+    let reloading = reload_events.iter().map(|e| e.player_id).collect::<Vec<_>>();
+    let mut rng = rand::thread_rng();
+    query.iter().for_each(|(ent, player, input, children, loadout)| {
         children.iter().for_each(|&child| {
-            let Ok((turret, trans)) = turret_query.get(child)
+            let Ok((mut turret, trans)) = turret_query.get_mut(child)
                 else { return; };
-            let angle = turret.direction.y.atan2(turret.direction.x);
-            let start_pos = trans.translation().truncate() + turret.direction * BULLET_OFFSET;
-            commands.spawn((
-                Name::from("Bullet"),
-                Bullet {
-                    owner: Some(ent),
-                    lifetime: BULLET_LIFETIME,
-                    damage: BULLET_DAMAGE,
-                },
-                AutoSorted,
-                SpriteEnum::Bullet,
-                TransformBundle::from_transform(Transform {
-                    translation: start_pos.extend(BULLET_LAYER),
-                    rotation: Quat::from_axis_angle(Vec3::new(0., 0., 1.), angle),
-                    scale: Vec3::new(1., 1., 1.) * BULLET_SCALE,
-                    ..default()
-                }),
-                Object::new(),
-                Velocity::linear(turret.direction * turret.bullet_speed),
-                RigidBody::KinematicVelocityBased,
-                Collider::ball(BULLET_COLLIDER_RADIUS),
-                Sensor,
-                ActiveEvents::COLLISION_EVENTS,
-            ));
+            turret.cooldown = (turret.cooldown - time.delta_seconds()).max(0.);
+
+            let Some(weapon) = weapon_assets.get(&definitions, &turret.weapon) else {
+                if input.fire_bullet {
+                    warn!("Turret has unknown weapon '{}'", turret.weapon);
+                }
+                return;
+            };
+
+            turret.state.tick(weapon, time.delta_seconds() * turret.reload_multiplier);
+            if reloading.contains(&player.id) {
+                turret.state.start_reload(weapon);
+            }
+            if !input.fire_bullet || turret.cooldown > 0. || !turret.state.can_fire(weapon) { return; }
+
+            // Shooting gives up the protection a tank gets after spawning
+            commands.entity(ent).remove::<SpawnProtection>();
+            turret.cooldown = 1. / (weapon.fire_rate * turret.reload_multiplier);
+            let damage = weapon.damage * turret.damage_multiplier;
+            turret.state.fired(weapon);
+            let (bounces, bounce_speed_loss) = match loadout.and_then(|loadout| loadout.bullet_bounces()) {
+                Some((extra, speed_loss)) => (weapon.bounces + extra, weapon.bounce_speed_loss.max(speed_loss)),
+                None => (weapon.bounces, weapon.bounce_speed_loss),
+            };
+
+            weapon.pellet_angles(rng.gen()).into_iter().for_each(|offset| {
+                let direction = Vec2::from_angle(offset).rotate(turret.direction);
+                let angle = direction.y.atan2(direction.x);
+                let start_pos = trans.translation().truncate() + direction * BULLET_OFFSET;
+                if let Some(hitscan) = weapon.hitscan {
+                    hitscan_writer.send(HitscanEvent {
+                        shooter: ent,
+                        origin: trans.translation().truncate(),
+                        muzzle: start_pos,
+                        direction,
+                        damage,
+                        hitscan,
+                    });
+                    return;
+                }
+
+                let mut bullet = commands.spawn((
+                    Name::from("Bullet"),
+                    Bullet {
+                        owner: Some(ent),
+                        lifetime: weapon.lifetime,
+                        damage,
+                        radius: weapon.collider_radius,
+                        bounces_left: bounces,
+                        bounce_speed_loss,
+                        explosion: weapon.explosion,
+                    },
+                    AutoSorted,
+                    weapon.sprite,
+                    TransformBundle::from_transform(Transform {
+                        translation: start_pos.extend(BULLET_LAYER),
+                        rotation: Quat::from_axis_angle(Vec3::new(0., 0., 1.), angle),
+                        scale: Vec3::new(1., 1., 1.) * weapon.scale,
+                        ..default()
+                    }),
+                    Object::new(),
+                    Velocity::linear(direction * weapon.speed * turret.bullet_speed_multiplier),
+                    RigidBody::KinematicVelocityBased,
+                    Collider::ball(weapon.collider_radius),
+                    Sensor,
+                    ActiveEvents::COLLISION_EVENTS,
+                ));
+                if let Some(homing) = weapon.homing {
+                    bullet.insert(Homing::new(homing, turret.direction));
+                }
+            });
         });
     });
 }
 
+/// Despawns the bullet and sets off its explosion, if it has one. Its lifetime is zeroed so
+/// the bullet systems running after this one leave it alone.
+fn destroy_bullet(
+    ent: Entity,
+    bullet: &mut Bullet,
+    position: Vec2,
+    commands: &mut Commands,
+    explode_writer: &mut EventWriter<ExplodeEvent>,
+) {
+    bullet.lifetime = 0.;
+    if let Some(explosion) = bullet.explosion {
+        explode_writer.send(ExplodeEvent { position, owner: bullet.owner, explosion });
+    }
+    commands.entity(ent).custom_despawn();
+}
+
 fn bullet_decay(
-    mut bullets: Query<(Entity, &mut Bullet)>,
+    mut bullets: Query<(Entity, &mut Bullet, &Transform)>,
+    mut explode_writer: EventWriter<ExplodeEvent>,
     time: Res<Time>,
     mut commands: Commands,
 ) {
-    bullets.iter_mut().for_each(|(ent, mut bullet)| {
+    bullets.iter_mut().for_each(|(ent, mut bullet, trans)| {
         bullet.lifetime -= time.delta_seconds();
         if bullet.lifetime <= 0. {
-            commands.entity(ent).custom_despawn();
+            destroy_bullet(ent, &mut bullet, trans.translation.truncate(), &mut commands, &mut explode_writer);
         }
     })
 }
 
+/// Looks ahead of every bullet for trees and walls. Bullets with bounces left are reflected off
+/// them, the rest are destroyed.
+fn bullet_obstacle_collision(
+    mut bullets: Query<(Entity, &mut Bullet, &mut Transform, &mut Velocity, &Object)>,
+    obstacles: Query<(), With<MapEntity>>,
+    rapier_context: Res<RapierContext>,
+    mut bounce_writer: EventWriter<OnBulletBounceEvent>,
+    mut explode_writer: EventWriter<ExplodeEvent>,
+    mut commands: Commands,
+    time: Res<Time>,
+) {
+    let is_obstacle = |entity| obstacles.get(entity).is_ok();
+    let filter = QueryFilter::new().exclude_sensors().predicate(&is_obstacle);
+
+    bullets.iter_mut().for_each(|(ent, mut bullet, mut trans, mut vel, object)| {
+        // Already despawned by `bullet_decay`
+        if bullet.lifetime <= 0. { return; }
+
+        let position = trans.translation.truncate();
+        let speed = vel.linvel.length();
+        let direction = vel.linvel.normalize_or_zero();
+        if direction == Vec2::ZERO { return; }
+
+        let Some((_, hit)) = rapier_context.cast_ray_and_get_normal(
+            position, direction, speed * time.delta_seconds() + bullet.radius, true, filter)
+            else { return; };
+
+        if bullet.bounces_left == 0 || hit.normal == Vec2::ZERO {
+            let impact = position + direction * hit.toi;
+            destroy_bullet(ent, &mut bullet, impact, &mut commands, &mut explode_writer);
+            return;
+        }
+
+        bullet.bounces_left -= 1;
+        let reflected = direction - 2. * direction.dot(hit.normal) * hit.normal;
+        vel.linvel = reflected * speed * (1. - bullet.bounce_speed_loss);
+
+        let bounce_position = position + direction * (hit.toi - bullet.radius).max(0.);
+        trans.translation = bounce_position.extend(trans.translation.z);
+        trans.rotation = Quat::from_axis_angle(Vec3::Z, reflected.y.atan2(reflected.x));
+
+        bounce_writer.send(OnBulletBounceEvent {
+            object_id: object.id,
+            position: bounce_position,
+            velocity: vel.linvel,
+        });
+    });
+}
+
 fn bullet_collision_sender(
     mut collision_events: EventReader<CollisionEvent>,
     mut bullet_event_wr: EventWriter<BulletCollisionEvent>,
@@ -134,43 +287,62 @@ fn bullet_collision_sender(
     });
 }
 
+/// Whether the attacker and victim are on the same side, so shouldn't hurt each other.
+pub fn is_friendly_fire(
+    attacker: Option<Entity>,
+    victim: Entity,
+    players: &Query<(&Player, Option<&AiTank>)>,
+    lobby: &Lobby,
+) -> bool {
+    let Some(attacker) = attacker else { return false; };
+    if attacker == victim { return false; }
+    let Ok([(attacker, attacker_ai), (victim, victim_ai)]) = players.get_many([attacker, victim])
+        else { return false; };
+    // AI tanks are all on the same side
+    (attacker_ai.is_some() && victim_ai.is_some()) || lobby.are_teammates(attacker.id, victim.id)
+}
+
+/// Damages the tank a bullet hit, unless it hit the front of a raised shield, which stops the bullet.
 fn bullet_collision_handler(
     mut events: EventReader<BulletCollisionEvent>,
-    mut kill_writer: EventWriter<OnKillEvent>,
-    mut death_writer: EventWriter<OnPlayerDeathEvent>,
-    mut health_writer: EventWriter<OnHealthChangedEvent>,
-    bullets: Query<&Bullet>,
-    mut healths: Query<(Entity, &mut Health)>,
-    players: Query<&Player>,
+    (mut damage_writer, mut explode_writer, mut block_writer):
+    (
+        EventWriter<OnDamageEvent>,
+        EventWriter<ExplodeEvent>,
+        EventWriter<OnShieldBlockEvent>,
+    ),
+    mut bullets: Query<(&mut Bullet, &Transform)>,
+    mut shields: Query<(&mut SkillLoadout, &Transform)>,
+    players: Query<(&Player, Option<&AiTank>)>,
     lobby: Res<Lobby>,
     mut commands: Commands,
 ) {
     events.iter().for_each(|e| {
-        let Ok((entity, mut health)) = healths.get_mut(e.player) else { return; };
-        let Ok(bullet) = bullets.get(e.bullet) else { return; };
-        health.health = (health.health - bullet.damage).clamp(0., health.max_health);
-        if health.health <= 0. {
-            commands.entity(entity).custom_despawn();
+        let Ok((mut bullet, trans)) = bullets.get_mut(e.bullet) else { return; };
+        // Already destroyed this frame
+        if bullet.lifetime <= 0. { return; }
+        let friendly = is_friendly_fire(bullet.owner, e.player, &players, &lobby);
+
+        let position = trans.translation.truncate();
+        if !friendly &&
+            let Ok((mut loadout, victim_trans)) = shields.get_mut(e.player) &&
+            loadout.shield_blocks(position - victim_trans.translation.truncate()) {
+            loadout.block(bullet.damage);
+            block_writer.send(OnShieldBlockEvent { position });
+            destroy_bullet(e.bullet, &mut bullet, position, &mut commands, &mut explode_writer);
+            return;
         }
 
-        if let Some(attacker) = bullet.owner &&
-            let Ok(&Player { id: victim_id, .. }) = players.get(entity) {
-            health_writer.send(OnHealthChangedEvent {
-                object_id: lobby.player_data.get(&victim_id).unwrap().object_id.unwrap(),
-                health: health.health,
-                max_health: health.max_health,
+        if !friendly {
+            damage_writer.send(OnDamageEvent {
+                victim: e.player,
+                attacker: bullet.owner,
+                damage: bullet.damage,
             });
+        }
 
-            if health.health <= 0. {
-                death_writer.send(OnPlayerDeathEvent { player_id: victim_id });
-
-                if let Ok(&Player { id: attacker_id, .. }) = players.get(attacker) {
-                    kill_writer.send(OnKillEvent {
-                        attacker_id,
-                        victim_id,
-                    });
-                }
-            }
+        if bullet.explosion.is_some() {
+            destroy_bullet(e.bullet, &mut bullet, position, &mut commands, &mut explode_writer);
         }
     })
-}
\ No newline at end of file
+}
@@ -0,0 +1,81 @@
+use std::collections::HashMap;
+use std::env;
+use bevy::log::warn;
+use bevy::prelude::Resource;
+use crate::simulation::map::{MapData, MapSource};
+use crate::simulation::server_sim::game_mode::ffa::FFA_NAME;
+
+const DEFAULT_REGEN: f32 = 5.;
+const DEFAULT_REGEN_DELAY: f32 = 5.;
+
+/// Server settings passed as `key=value` executable args, e.g. `map=procedural seed=42 mode=br`
+/// or `map=maps/arena.ron mode=tdm teams=3` or `mode=elim rounds=7 regen=0`.
+#[derive(Resource, Debug, Clone)]
+pub struct ServerConfig {
+    pub map: MapSource,
+    /// Name of a registered game mode, see `GameModeRegistry`.
+    pub mode: String,
+    /// Overrides how many teams a team mode is played with.
+    pub teams: Option<usize>,
+    /// How many rounds a round based mode is played as the best of.
+    pub rounds: Option<u32>,
+    /// Health regenerated per second by tanks out of combat, 0 turns regeneration off.
+    pub regen: f32,
+    /// Seconds after taking damage before a tank starts regenerating.
+    pub regen_delay: f32,
+}
+
+impl Default for ServerConfig {
+    fn default() -> Self {
+        ServerConfig {
+            map: MapSource::default(),
+            mode: FFA_NAME.to_owned(),
+            teams: None,
+            rounds: None,
+            regen: DEFAULT_REGEN,
+            regen_delay: DEFAULT_REGEN_DELAY,
+        }
+    }
+}
+
+impl ServerConfig {
+    pub fn from_args() -> Self {
+        let args = env::args()
+            .filter_map(|arg| arg.split_once('=')
+                .map(|(key, value)| (key.to_owned(), value.to_owned())))
+            .collect::<HashMap<String, String>>();
+
+        let seed = args.get("seed").and_then(|seed| seed.parse::<u64>().ok());
+        let map = match (args.get("map").map(String::as_str), seed) {
+            (Some("procedural"), _) | (None, Some(_)) => MapSource::Procedural {
+                seed: seed.unwrap_or_else(rand::random)
+            },
+            (Some("default"), _) | (None, None) => MapSource::Default,
+            (Some(path), _) if path.ends_with(".ron") => match MapData::load(path) {
+                Ok(map) => MapSource::Custom(map),
+                Err(e) => {
+                    warn!("Could not load map '{}': {}, using the default map", path, e);
+                    MapSource::Default
+                }
+            },
+            (Some(other), _) => {
+                warn!("Unknown map '{}', using the default map", other);
+                MapSource::Default
+            }
+        };
+
+        // Unknown modes are reported once the registered modes are known
+        let mode = args.get("mode").cloned().unwrap_or_else(|| FFA_NAME.to_owned());
+
+        let teams = args.get("teams").and_then(|teams| teams.parse::<usize>().ok());
+
+        let rounds = args.get("rounds").and_then(|rounds| rounds.parse::<u32>().ok());
+
+        let regen = args.get("regen").and_then(|regen| regen.parse::<f32>().ok())
+            .map_or(DEFAULT_REGEN, |regen| regen.max(0.));
+        let regen_delay = args.get("regen_delay").and_then(|delay| delay.parse::<f32>().ok())
+            .map_or(DEFAULT_REGEN_DELAY, |delay| delay.max(0.));
+
+        ServerConfig { map, mode, teams, rounds, regen, regen_delay }
+    }
+}
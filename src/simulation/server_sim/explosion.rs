@@ -0,0 +1,161 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::ExternalImpulse;
+use crate::ServerSet::ServerUpdate;
+use crate::simulation::Lobby;
+use crate::simulation::server_sim::ai::AiTank;
+use crate::simulation::server_sim::bullet::BulletSystemStage::CollisionHandle;
+use crate::simulation::server_sim::bullet::{is_friendly_fire, OnShieldBlockEvent};
+use crate::simulation::server_sim::skill::SkillLoadout;
+use crate::simulation::server_sim::player::{Health, OnDamageEvent, Player, PlayerSystemStage};
+use crate::simulation::server_sim::weapon::ExplosionDefinition;
+use crate::utils::prefabs::TANK_COLLIDER_RADIUS;
+
+pub struct ExplosionPlugin;
+
+impl Plugin for ExplosionPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_event::<ExplodeEvent>()
+            .add_event::<OnExplosionEvent>()
+            .add_system(apply_explosions
+                .in_set(ServerUpdate)
+                .after(CollisionHandle)
+                .before(PlayerSystemStage::ApplyDamage));
+    }
+}
+
+/// A projectile went off.
+pub struct ExplodeEvent {
+    pub position: Vec2,
+    pub owner: Option<Entity>,
+    pub explosion: ExplosionDefinition,
+}
+
+/// Sent to clients so they can show the blast.
+pub struct OnExplosionEvent {
+    pub position: Vec2,
+    pub radius: f32,
+}
+
+/// Damages and pushes away every tank in the blast radius, less the further they are from
+/// the center. Damage goes through `OnDamageEvent` with the shooter as the attacker, so kills
+/// are credited like any other. Shields facing the blast block its damage.
+fn apply_explosions(
+    mut explode_events: EventReader<ExplodeEvent>,
+    mut targets: Query<(Entity, &Transform, &mut ExternalImpulse, Option<&mut SkillLoadout>), With<Health>>,
+    players: Query<(&Player, Option<&AiTank>)>,
+    lobby: Res<Lobby>,
+    mut damage_writer: EventWriter<OnDamageEvent>,
+    mut explosion_writer: EventWriter<OnExplosionEvent>,
+    mut block_writer: EventWriter<OnShieldBlockEvent>,
+) {
+    explode_events.iter().for_each(|e| {
+        let ExplosionDefinition { radius, damage, knockback, damages_owner } = e.explosion;
+
+        targets.iter_mut().for_each(|(victim, trans, mut impulse, loadout)| {
+            let offset = trans.translation.truncate() - e.position;
+            let falloff = 1. - offset.length() / radius;
+            if falloff <= 0. { return; }
+
+            impulse.impulse += offset.normalize_or_zero() * knockback * falloff;
+
+            let is_owner = e.owner == Some(victim);
+            if (is_owner && !damages_owner) || is_friendly_fire(e.owner, victim, &players, &lobby) {
+                return;
+            }
+            if let Some(mut loadout) = loadout && loadout.shield_blocks(-offset) {
+                loadout.block(damage * falloff);
+                block_writer.send(OnShieldBlockEvent {
+                    position: trans.translation.truncate() - offset.normalize_or_zero() * TANK_COLLIDER_RADIUS,
+                });
+                return;
+            }
+            damage_writer.send(OnDamageEvent {
+                victim,
+                attacker: e.owner,
+                damage: damage * falloff,
+            });
+        });
+
+        explosion_writer.send(OnExplosionEvent { position: e.position, radius });
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXPLOSION: ExplosionDefinition = ExplosionDefinition {
+        radius: 100.,
+        damage: 40.,
+        knockback: 80.,
+        damages_owner: false,
+    };
+
+    fn explosion_app() -> App {
+        let mut app = App::new();
+        app
+            .init_resource::<Lobby>()
+            .add_event::<ExplodeEvent>()
+            .add_event::<OnExplosionEvent>()
+            .add_event::<OnDamageEvent>()
+            .add_event::<OnShieldBlockEvent>()
+            .add_system(apply_explosions);
+        app
+    }
+
+    fn spawn_tank(app: &mut App, id: u64, position: Vec2) -> Entity {
+        app.world.spawn((
+            Player::new(id),
+            Health::default(),
+            Transform::from_translation(position.extend(0.)),
+            ExternalImpulse::default(),
+        )).id()
+    }
+
+    /// Sets off the explosion at the origin and returns the damage every tank took.
+    fn explode(app: &mut App, owner: Option<Entity>, explosion: ExplosionDefinition) -> Vec<(Entity, Option<Entity>, f32)> {
+        app.world.send_event(ExplodeEvent { position: Vec2::ZERO, owner, explosion });
+        app.update();
+        let events = app.world.resource::<Events<OnDamageEvent>>();
+        events.get_reader().iter(events).map(|e| (e.victim, e.attacker, e.damage)).collect()
+    }
+
+    #[test]
+    fn damage_falls_off_with_distance() {
+        let mut app = explosion_app();
+        let near = spawn_tank(&mut app, 1, Vec2::new(25., 0.));
+        let far = spawn_tank(&mut app, 2, Vec2::new(0., 75.));
+        let outside = spawn_tank(&mut app, 3, Vec2::new(150., 0.));
+
+        let damage = explode(&mut app, None, EXPLOSION);
+        let damage_to = |tank| damage.iter().find(|(victim, _, _)| *victim == tank).map(|(_, _, damage)| *damage);
+        assert_eq!(damage_to(near), Some(30.));
+        assert_eq!(damage_to(far), Some(10.));
+        assert_eq!(damage_to(outside), None);
+    }
+
+    #[test]
+    fn knockback_pushes_away_from_the_center() {
+        let mut app = explosion_app();
+        let tank = spawn_tank(&mut app, 1, Vec2::new(-50., 0.));
+        let outside = spawn_tank(&mut app, 2, Vec2::new(0., 150.));
+
+        explode(&mut app, None, EXPLOSION);
+        assert_eq!(app.world.get::<ExternalImpulse>(tank).unwrap().impulse, Vec2::new(-40., 0.));
+        assert_eq!(app.world.get::<ExternalImpulse>(outside).unwrap().impulse, Vec2::ZERO);
+    }
+
+    #[test]
+    fn owner_is_only_damaged_if_the_explosion_says_so() {
+        let mut app = explosion_app();
+        let owner = spawn_tank(&mut app, 1, Vec2::new(50., 0.));
+
+        assert!(explode(&mut app, Some(owner), EXPLOSION).is_empty());
+        // Still pushed away, only the damage is skipped
+        assert_eq!(app.world.get::<ExternalImpulse>(owner).unwrap().impulse, Vec2::new(40., 0.));
+
+        let damage = explode(&mut app, Some(owner), ExplosionDefinition { damages_owner: true, ..EXPLOSION });
+        assert_eq!(damage, vec![(owner, Some(owner), 20.)]);
+    }
+}
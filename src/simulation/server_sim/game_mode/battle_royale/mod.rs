@@ -0,0 +1,159 @@
+mod systems;
+
+use bevy::app::App;
+use bevy::math::Vec2;
+use bevy::prelude::*;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use systems::*;
+use crate::AppState;
+use crate::ServerSet::ServerUpdate;
+use crate::simulation::server_sim::game_mode::{GameMode, GameModeRules, in_game_mode, is_match_over, RespawnPolicy, score_by_kills, score_spawn_safety, spawn_safest};
+use crate::simulation::server_sim::player::PlayerSystemStage::ApplyDamage;
+
+pub const BATTLE_ROYALE_NAME: &str = "br";
+
+/// Last tank standing, with no respawns and a zone that closes in over several phases.
+pub struct BattleRoyaleMode;
+
+impl GameMode for BattleRoyaleMode {
+    fn rules(&self) -> GameModeRules {
+        GameModeRules {
+            name: BATTLE_ROYALE_NAME,
+            display_name: "Battle Royale",
+            time_limit: None,
+            restart_delay: 10.,
+            respawn: RespawnPolicy::Never,
+            teams: 0,
+            select_spawn: spawn_safest,
+            score_spawn: score_spawn_safety,
+            score: score_by_kills,
+            extra_score: None,
+        }
+    }
+}
+
+impl Plugin for BattleRoyaleMode {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(
+                (
+                    init_zone.run_if(not(resource_exists::<BattleRoyaleZone>())),
+                    zone_clock.run_if(resource_exists::<BattleRoyaleZone>()),
+                    zone_damage.run_if(resource_exists::<BattleRoyaleZone>())
+                        .after(zone_clock)
+                        .before(ApplyDamage),
+                    last_tank_standing.run_if(resource_exists::<BattleRoyaleZone>()
+                        .and_then(not(is_match_over)))
+                        .after(ApplyDamage),
+                ).in_set(ServerUpdate).distributive_run_if(in_game_mode(BATTLE_ROYALE_NAME))
+            )
+            .add_system(remove_zone.in_schedule(OnExit(AppState::InGame)));
+    }
+}
+
+pub struct ZonePhase {
+    pub wait: f32,
+    pub shrink: f32,
+    pub radius_fraction: f32,
+    pub damage_per_second: f32,
+}
+
+/// The first wait doubles as a warmup, players can still join until the zone starts closing.
+pub const ZONE_PHASES: [ZonePhase; 4] = [
+    ZonePhase { wait: 45., shrink: 30., radius_fraction: 0.6, damage_per_second: 2. },
+    ZonePhase { wait: 30., shrink: 25., radius_fraction: 0.35, damage_per_second: 5. },
+    ZonePhase { wait: 25., shrink: 20., radius_fraction: 0.15, damage_per_second: 10. },
+    ZonePhase { wait: 20., shrink: 15., radius_fraction: 0., damage_per_second: 20. },
+];
+
+/// Server-authoritative zone state, replicated to clients as-is.
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct BattleRoyaleZone {
+    pub center: Vec2,
+    pub radius: f32,
+    pub start_center: Vec2,
+    pub start_radius: f32,
+    pub target_center: Vec2,
+    pub target_radius: f32,
+    pub initial_radius: f32,
+    pub phase: usize,
+    pub shrinking: bool,
+    pub time_remaining: f32,
+    pub damage_per_second: f32,
+}
+
+impl BattleRoyaleZone {
+    pub fn new(center: Vec2, radius: f32) -> Self {
+        let mut zone = BattleRoyaleZone {
+            center,
+            radius,
+            start_center: center,
+            start_radius: radius,
+            target_center: center,
+            target_radius: radius,
+            initial_radius: radius,
+            phase: 0,
+            shrinking: false,
+            time_remaining: 0.,
+            damage_per_second: 0.,
+        };
+        zone.start_waiting(&mut rand::thread_rng());
+        zone
+    }
+
+    pub fn contains(&self, point: Vec2) -> bool {
+        point.distance(self.center) <= self.radius
+    }
+
+    pub fn is_warmup(&self) -> bool {
+        self.phase == 0 && !self.shrinking
+    }
+
+    pub fn is_closed(&self) -> bool {
+        self.phase >= ZONE_PHASES.len()
+    }
+
+    pub fn tick(&mut self, delta_seconds: f32, rng: &mut impl Rng) {
+        let Some(phase) = ZONE_PHASES.get(self.phase) else { return; };
+
+        self.time_remaining -= delta_seconds;
+
+        if self.shrinking {
+            let progress = 1. - (self.time_remaining / phase.shrink).clamp(0., 1.);
+            self.center = self.start_center.lerp(self.target_center, progress);
+            self.radius = self.start_radius + (self.target_radius - self.start_radius) * progress;
+        }
+
+        if self.time_remaining > 0. { return; }
+
+        if self.shrinking {
+            self.center = self.target_center;
+            self.radius = self.target_radius;
+            self.shrinking = false;
+            self.phase += 1;
+            self.start_waiting(rng);
+        } else {
+            self.shrinking = true;
+            self.start_center = self.center;
+            self.start_radius = self.radius;
+            self.time_remaining = phase.shrink;
+            self.damage_per_second = phase.damage_per_second;
+        }
+    }
+
+    /// Picks the next zone inside the current one, so players can see where to head before it shrinks.
+    fn start_waiting(&mut self, rng: &mut impl Rng) {
+        let Some(phase) = ZONE_PHASES.get(self.phase) else {
+            self.time_remaining = 0.;
+            return;
+        };
+
+        self.target_radius = self.initial_radius * phase.radius_fraction;
+        let max_offset = (self.radius - self.target_radius).max(0.);
+        let angle: f32 = rng.gen_range(0.0..std::f32::consts::TAU);
+        let offset: f32 = rng.gen_range(0.0..=1.0);
+        self.target_center = self.center + Vec2::from_angle(angle) * max_offset * offset;
+        self.time_remaining = phase.wait;
+    }
+}
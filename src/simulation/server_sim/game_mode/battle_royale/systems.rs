@@ -0,0 +1,73 @@
+use bevy::prelude::{Commands, Entity, EventWriter, Local, Query, Res, ResMut, Time, Transform, With};
+use crate::simulation::Lobby;
+use crate::simulation::map::MapData;
+use crate::simulation::server_sim::boundary::{zone_tick_damage, ZONE_DAMAGE_INTERVAL};
+use crate::simulation::server_sim::game_mode::{ActiveGameMode, MatchWinner, OnMatchEndEvent};
+use crate::simulation::server_sim::game_mode::battle_royale::BattleRoyaleZone;
+use crate::simulation::server_sim::player::{Health, OnDamageEvent, Player};
+
+pub fn init_zone(
+    map: Option<Res<MapData>>,
+    mut commands: Commands,
+) {
+    let Some(map) = map else { return; };
+    let [min, max] = map.bounding_box();
+    let center = (min + max) / 2.;
+    commands.insert_resource(BattleRoyaleZone::new(center, center.distance(max)));
+}
+
+pub fn zone_clock(
+    mut zone: ResMut<BattleRoyaleZone>,
+    mut mode: ResMut<ActiveGameMode>,
+    time: Res<Time>,
+) {
+    zone.tick(time.delta_seconds(), &mut rand::thread_rng());
+    mode.spawns_locked = !zone.is_warmup();
+}
+
+pub fn zone_damage(
+    zone: Res<BattleRoyaleZone>,
+    players: Query<(Entity, &Transform), With<Player>>,
+    mut damage_writer: EventWriter<OnDamageEvent>,
+    mut timer: Local<f32>,
+    time: Res<Time>,
+) {
+    *timer -= time.delta_seconds();
+    if *timer > 0. { return; }
+    *timer += ZONE_DAMAGE_INTERVAL;
+
+    players.iter()
+        .filter(|(_, trans)| !zone.contains(trans.translation.truncate()))
+        .for_each(|(victim, _)| {
+            damage_writer.send(OnDamageEvent {
+                victim,
+                attacker: None,
+                damage: zone_tick_damage(zone.damage_per_second),
+            });
+        });
+}
+
+pub fn last_tank_standing(
+    zone: Res<BattleRoyaleZone>,
+    mut end_writer: EventWriter<OnMatchEndEvent>,
+    players: Query<(&Player, &Health)>,
+    lobby: Res<Lobby>,
+) {
+    if zone.is_warmup() || lobby.player_data.is_empty() { return; }
+
+    // Dead tanks are only despawned at the end of the frame, so go by health
+    let alive = players.iter()
+        .filter(|(_, health)| health.health > 0.)
+        .map(|(player, _)| player.id)
+        .collect::<Vec<_>>();
+
+    let last_standing = alive.len() == 1 && lobby.player_data.len() > 1;
+    if !last_standing && !alive.is_empty() { return; }
+
+    let winner = alive.first().map_or(MatchWinner::Nobody, |&id| MatchWinner::Player(id));
+    end_writer.send(OnMatchEndEvent { winner });
+}
+
+pub fn remove_zone(mut commands: Commands) {
+    commands.remove_resource::<BattleRoyaleZone>();
+}
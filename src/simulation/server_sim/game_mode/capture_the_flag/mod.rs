@@ -0,0 +1,108 @@
+mod systems;
+
+use bevy::app::App;
+use bevy::math::Vec2;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use systems::*;
+use crate::ServerSet::ServerUpdate;
+use crate::simulation::PlayerData;
+use crate::simulation::server_sim::game_mode::{GameMode, GameModeRules, in_game_mode, is_match_over, RespawnPolicy, score_spawn_safety};
+use crate::simulation::server_sim::game_mode::teams::spawn_with_team;
+use crate::simulation::team::Team;
+use crate::utils::networking::messages::PlayerId;
+
+pub const CAPTURE_THE_FLAG_NAME: &str = "ctf";
+
+const CAPTURE_LIMIT: i32 = 3;
+const FLAG_TOUCH_RADIUS: f32 = 80.;
+const FLAG_RETURN_TIME: f32 = 30.;
+const CARRIED_FLAG_OFFSET: Vec2 = Vec2::new(30., -20.);
+
+/// Each team has a flag at its base. Drive over the enemy flag and bring it back to your own flag to
+/// capture it, the first team to 3 captures wins.
+pub struct CaptureTheFlagMode;
+
+impl GameMode for CaptureTheFlagMode {
+    fn rules(&self) -> GameModeRules {
+        GameModeRules {
+            name: CAPTURE_THE_FLAG_NAME,
+            display_name: "Capture The Flag",
+            time_limit: Some(600.),
+            restart_delay: 10.,
+            respawn: RespawnPolicy::After(5.),
+            teams: 2,
+            select_spawn: spawn_with_team,
+            score_spawn: score_spawn_safety,
+            score: score_by_captures,
+            extra_score: Some("captures"),
+        }
+    }
+}
+
+impl Plugin for CaptureTheFlagMode {
+    fn build(&self, app: &mut App) {
+        app
+            .add_event::<FlagEvent>()
+            .add_systems(
+                (
+                    spawn_flags,
+                    carry_flags,
+                    touch_flags,
+                    return_dropped_flags,
+                    end_match_on_capture_limit.run_if(not(is_match_over)),
+                ).chain().in_set(ServerUpdate).distributive_run_if(in_game_mode(CAPTURE_THE_FLAG_NAME))
+            );
+    }
+}
+
+pub fn score_by_captures(data: &PlayerData) -> i32 {
+    data.captures as i32
+}
+
+#[derive(Component)]
+pub struct Flag {
+    pub team: Team,
+    pub base: Vec2,
+    pub state: FlagState,
+}
+
+impl Flag {
+    pub fn new(team: Team, base: Vec2) -> Self {
+        Flag {
+            team,
+            base,
+            state: FlagState::AtBase,
+        }
+    }
+
+    pub fn return_to_base(&mut self, trans: &mut Transform) {
+        self.state = FlagState::AtBase;
+        trans.translation = self.base.extend(trans.translation.z);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FlagState {
+    AtBase,
+    Carried(PlayerId),
+    /// Goes back to base by itself once the timer runs out.
+    Dropped { return_in: f32 },
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FlagAction {
+    Taken,
+    Dropped,
+    Returned,
+    Captured,
+}
+
+/// Something happened to a team's flag, announced to every client.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FlagEvent {
+    pub action: FlagAction,
+    pub team: Team,
+    /// Who did it, nobody when a dropped flag returns on its own.
+    pub player_id: Option<PlayerId>,
+}
@@ -0,0 +1,149 @@
+use bevy::math::Vec2;
+use bevy::prelude::{Commands, Entity, EventWriter, Query, Res, ResMut, Time, Transform, With, Without};
+use crate::simulation::Lobby;
+use crate::simulation::events::OnPlayerDataChangedEvent;
+use crate::simulation::server_sim::game_mode::{ActiveGameMode, MatchWinner, OnMatchEndEvent};
+use crate::simulation::server_sim::game_mode::capture_the_flag::{CAPTURE_LIMIT, CARRIED_FLAG_OFFSET, Flag, FLAG_RETURN_TIME, FLAG_TOUCH_RADIUS, FlagAction, FlagEvent, FlagState, score_by_captures};
+use crate::simulation::server_sim::game_mode::teams::{rank_teams, team_spawn_points};
+use crate::simulation::server_sim::player::Player;
+use crate::simulation::server_sim::spawn::SpawnPoint;
+use crate::utils::prefabs::flag;
+
+/// Each flag's base is in the middle of its team's spawn points.
+pub fn spawn_flags(
+    flags: Query<&Flag>,
+    spawn_points: Query<&Transform, With<SpawnPoint>>,
+    mode: Res<ActiveGameMode>,
+    mut commands: Commands,
+) {
+    if !flags.is_empty() { return; }
+
+    let spawn_points = spawn_points.iter()
+        .map(|trans| trans.translation.truncate())
+        .collect::<Vec<_>>();
+
+    mode.teams.iter().for_each(|&team| {
+        let Some(group) = team_spawn_points(&spawn_points, &mode.teams, team) else { return; };
+        let base = group.iter().sum::<Vec2>() / group.len() as f32;
+        commands.spawn(flag(team, base));
+    });
+}
+
+/// Carried flags follow their carrier, and are dropped where they were once the carrier is gone.
+pub fn carry_flags(
+    mut flags: Query<(&mut Flag, &mut Transform)>,
+    players: Query<(&Player, &Transform), Without<Flag>>,
+    mut feed_writer: EventWriter<FlagEvent>,
+) {
+    flags.iter_mut().for_each(|(mut flag, mut trans)| {
+        let FlagState::Carried(carrier_id) = flag.state else { return; };
+
+        match players.iter().find(|(player, _)| player.id == carrier_id) {
+            Some((_, player_trans)) => {
+                let position = player_trans.translation.truncate() + CARRIED_FLAG_OFFSET;
+                trans.translation = position.extend(trans.translation.z);
+            }
+            None => {
+                flag.state = FlagState::Dropped { return_in: FLAG_RETURN_TIME };
+                feed_writer.send(FlagEvent {
+                    action: FlagAction::Dropped,
+                    team: flag.team,
+                    player_id: Some(carrier_id),
+                });
+            }
+        }
+    });
+}
+
+/// Enemies pick flags up, teammates return dropped ones, and carriers capture at their own flag.
+pub fn touch_flags(
+    mut flags: Query<(Entity, &mut Flag, &mut Transform)>,
+    players: Query<(&Player, &Transform), Without<Flag>>,
+    mut lobby: ResMut<Lobby>,
+    mut feed_writer: EventWriter<FlagEvent>,
+    mut changed_writer: EventWriter<OnPlayerDataChangedEvent>,
+) {
+    players.iter().for_each(|(player, player_trans)| {
+        let Some(team) = lobby.team_of(player.id) else { return; };
+        let position = player_trans.translation.truncate();
+
+        let touching = flags.iter()
+            .filter(|(_, _, trans)| trans.translation.truncate().distance(position) < FLAG_TOUCH_RADIUS)
+            .map(|(entity, _, _)| entity)
+            .collect::<Vec<_>>();
+
+        touching.into_iter().for_each(|entity| {
+            let carried = flags.iter()
+                .find(|(_, flag, _)| flag.state == FlagState::Carried(player.id))
+                .map(|(entity, _, _)| entity);
+            let Ok((_, mut flag, mut trans)) = flags.get_mut(entity) else { return; };
+
+            match flag.state {
+                FlagState::AtBase | FlagState::Dropped { .. } if flag.team != team && carried.is_none() => {
+                    flag.state = FlagState::Carried(player.id);
+                    feed_writer.send(FlagEvent {
+                        action: FlagAction::Taken,
+                        team: flag.team,
+                        player_id: Some(player.id),
+                    });
+                }
+                FlagState::Dropped { .. } if flag.team == team => {
+                    flag.return_to_base(&mut trans);
+                    feed_writer.send(FlagEvent {
+                        action: FlagAction::Returned,
+                        team: flag.team,
+                        player_id: Some(player.id),
+                    });
+                }
+                FlagState::AtBase if flag.team == team => {
+                    let Some(carried) = carried else { return; };
+                    let Ok((_, mut enemy_flag, mut enemy_trans)) = flags.get_mut(carried) else { return; };
+                    enemy_flag.return_to_base(&mut enemy_trans);
+
+                    if let Some(data) = lobby.player_data.get_mut(&player.id) {
+                        data.captures += 1;
+                        changed_writer.send(OnPlayerDataChangedEvent { player_id: player.id });
+                    }
+
+                    feed_writer.send(FlagEvent {
+                        action: FlagAction::Captured,
+                        team: enemy_flag.team,
+                        player_id: Some(player.id),
+                    });
+                }
+                _ => {}
+            }
+        });
+    });
+}
+
+pub fn return_dropped_flags(
+    mut flags: Query<(&mut Flag, &mut Transform)>,
+    mut feed_writer: EventWriter<FlagEvent>,
+    time: Res<Time>,
+) {
+    flags.iter_mut().for_each(|(mut flag, mut trans)| {
+        let FlagState::Dropped { return_in } = &mut flag.state else { return; };
+        *return_in -= time.delta_seconds();
+        if *return_in > 0. { return; }
+
+        flag.return_to_base(&mut trans);
+        feed_writer.send(FlagEvent {
+            action: FlagAction::Returned,
+            team: flag.team,
+            player_id: None,
+        });
+    });
+}
+
+pub fn end_match_on_capture_limit(
+    lobby: Res<Lobby>,
+    mode: Res<ActiveGameMode>,
+    mut end_writer: EventWriter<OnMatchEndEvent>,
+) {
+    let Some(&(team, captures)) = rank_teams(&lobby, &mode.teams, score_by_captures).first()
+        else { return; };
+    if captures >= CAPTURE_LIMIT {
+        end_writer.send(OnMatchEndEvent { winner: MatchWinner::Team(team) });
+    }
+}
@@ -0,0 +1,102 @@
+mod systems;
+
+use bevy::app::App;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use systems::*;
+use crate::AppState;
+use crate::ServerSet::ServerUpdate;
+use crate::simulation::server_sim::game_mode::{GameMode, GameModeRules, in_game_mode, is_match_over, RespawnPolicy, score_by_kills, score_spawn_safety};
+use crate::simulation::server_sim::game_mode::teams::spawn_with_team;
+use crate::simulation::server_sim::player::PlayerSystemStage::ApplyDamage;
+use crate::simulation::server_sim::spawn::spawn_player_system;
+use crate::simulation::team::Team;
+
+pub const ELIMINATION_NAME: &str = "elim";
+
+const DEFAULT_ROUNDS: u32 = 5;
+const ROUND_LENGTH: f32 = 120.;
+const INTERMISSION_LENGTH: f32 = 5.;
+/// Players can join freely before the first round.
+const WARMUP_LENGTH: f32 = 15.;
+
+/// Short rounds between teams without respawns, the last team with tanks left wins the round.
+/// Best of 5 rounds by default, `rounds=<n>` changes it.
+pub struct EliminationMode;
+
+impl GameMode for EliminationMode {
+    fn rules(&self) -> GameModeRules {
+        GameModeRules {
+            name: ELIMINATION_NAME,
+            display_name: "Elimination",
+            time_limit: None,
+            restart_delay: 10.,
+            respawn: RespawnPolicy::Never,
+            teams: 2,
+            select_spawn: spawn_with_team,
+            score_spawn: score_spawn_safety,
+            score: score_by_kills,
+            extra_score: None,
+        }
+    }
+}
+
+impl Plugin for EliminationMode {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(
+                (
+                    init_rounds.run_if(not(resource_exists::<RoundState>())),
+                    // Runs before the clock so the despawns from the end of an intermission
+                    // are applied before anyone is spawned again
+                    start_round.run_if(resource_exists::<RoundState>()).before(spawn_player_system),
+                    round_clock.run_if(resource_exists::<RoundState>()),
+                    end_round.run_if(resource_exists::<RoundState>()).after(ApplyDamage),
+                ).chain().in_set(ServerUpdate)
+                    .distributive_run_if(in_game_mode(ELIMINATION_NAME))
+                    .distributive_run_if(not(is_match_over))
+            )
+            .add_system(remove_rounds.in_schedule(OnExit(AppState::InGame)));
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum RoundPhase {
+    /// Counting down to the next round, the first countdown is the warmup.
+    Intermission { time_remaining: f32 },
+    /// Tanks from the last round are gone, everyone spawns this frame.
+    Starting,
+    Playing { time_remaining: f32 },
+}
+
+/// The state of a best of N match, replicated to clients.
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RoundState {
+    /// Starts at 0 during the warmup.
+    pub round: u32,
+    pub best_of: u32,
+    pub phase: RoundPhase,
+    pub wins: Vec<(Team, u32)>,
+    /// Who took the last round, `None` for a draw.
+    pub last_winner: Option<Team>,
+}
+
+impl RoundState {
+    pub fn new(best_of: u32, teams: &[Team]) -> Self {
+        RoundState {
+            round: 0,
+            best_of,
+            phase: RoundPhase::Intermission { time_remaining: WARMUP_LENGTH },
+            wins: teams.iter().map(|&team| (team, 0)).collect(),
+            last_winner: None,
+        }
+    }
+
+    pub fn wins_needed(&self) -> u32 {
+        self.best_of / 2 + 1
+    }
+
+    pub fn wins_of(&self, team: Team) -> u32 {
+        self.wins.iter().find(|(other, _)| *other == team).map_or(0, |(_, wins)| *wins)
+    }
+}
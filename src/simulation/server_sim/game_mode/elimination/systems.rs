@@ -0,0 +1,120 @@
+use bevy::prelude::{Commands, Entity, EventWriter, Query, Res, ResMut, Time, With};
+use crate::simulation::Lobby;
+use crate::simulation::events::OnRespawnTimerFinish;
+use crate::simulation::server_sim::config::ServerConfig;
+use crate::simulation::server_sim::game_mode::{ActiveGameMode, MatchWinner, OnMatchEndEvent};
+use crate::simulation::server_sim::game_mode::elimination::{DEFAULT_ROUNDS, INTERMISSION_LENGTH, ROUND_LENGTH, RoundPhase, RoundState};
+use crate::simulation::server_sim::player::{Health, Player};
+use crate::simulation::team::Team;
+use crate::utils::commands::despawn::CustomDespawnExt;
+
+pub fn init_rounds(
+    config: Res<ServerConfig>,
+    mode: Res<ActiveGameMode>,
+    mut commands: Commands,
+) {
+    let best_of = config.rounds.unwrap_or(DEFAULT_ROUNDS).max(1);
+    commands.insert_resource(RoundState::new(best_of, &mode.teams));
+}
+
+/// Everyone is spawned fresh the frame after the last round's tanks were despawned,
+/// going through the same path as a respawn.
+pub fn start_round(
+    mut rounds: ResMut<RoundState>,
+    lobby: Res<Lobby>,
+    mut respawn_writer: EventWriter<OnRespawnTimerFinish>,
+) {
+    if rounds.phase != RoundPhase::Starting { return; }
+
+    lobby.player_data.keys().for_each(|&player_id| {
+        respawn_writer.send(OnRespawnTimerFinish { player_id });
+    });
+    rounds.round += 1;
+    rounds.phase = RoundPhase::Playing { time_remaining: ROUND_LENGTH };
+}
+
+pub fn round_clock(
+    mut rounds: ResMut<RoundState>,
+    mut mode: ResMut<ActiveGameMode>,
+    players: Query<Entity, With<Player>>,
+    mut commands: Commands,
+    time: Res<Time>,
+) {
+    match &mut rounds.phase {
+        RoundPhase::Intermission { time_remaining } => {
+            *time_remaining -= time.delta_seconds();
+            if *time_remaining <= 0. {
+                players.iter().for_each(|entity| commands.entity(entity).custom_despawn());
+                rounds.phase = RoundPhase::Starting;
+            }
+        }
+        RoundPhase::Playing { time_remaining } => {
+            *time_remaining = (*time_remaining - time.delta_seconds()).max(0.);
+        }
+        RoundPhase::Starting => {}
+    }
+
+    // Anyone joining mid round sits it out
+    mode.spawns_locked = matches!(rounds.phase, RoundPhase::Playing { .. });
+}
+
+/// The round goes to the last team with tanks left, or to the team with the most tanks left
+/// once the round runs out of time. Drawn rounds still count as played, so once `best_of` rounds
+/// are over the match goes to the team with the most wins, or nobody.
+pub fn end_round(
+    mut rounds: ResMut<RoundState>,
+    mode: Res<ActiveGameMode>,
+    players: Query<(&Player, &Health)>,
+    lobby: Res<Lobby>,
+    mut end_writer: EventWriter<OnMatchEndEvent>,
+) {
+    let RoundPhase::Playing { time_remaining } = rounds.phase else { return; };
+
+    // Dead tanks are only despawned at the end of the frame, so go by health
+    let alive = mode.teams.iter().map(|&team| {
+        (team, players.iter()
+            .filter(|(player, health)| health.health > 0. && lobby.team_of(player.id) == Some(team))
+            .count())
+    }).filter(|(_, count)| *count > 0).collect::<Vec<(Team, usize)>>();
+
+    let populated_teams = mode.teams.iter()
+        .filter(|&&team| lobby.player_data.values().any(|data| data.team == Some(team)))
+        .count();
+
+    let eliminated = alive.len() <= 1 && populated_teams > 1;
+    if !eliminated && time_remaining > 0. { return; }
+
+    let most_alive = alive.iter().map(|(_, count)| *count).max().unwrap_or(0);
+    let leaders = alive.iter().filter(|(_, count)| *count == most_alive).collect::<Vec<_>>();
+    let winner = match leaders.as_slice() {
+        [(team, _)] => Some(*team),
+        _ => None,
+    };
+
+    rounds.last_winner = winner;
+    rounds.phase = RoundPhase::Intermission { time_remaining: INTERMISSION_LENGTH };
+
+    let wins_needed = rounds.wins_needed();
+    if let Some(winner) = winner &&
+        let Some((_, wins)) = rounds.wins.iter_mut().find(|(team, _)| *team == winner) {
+        *wins += 1;
+        if *wins >= wins_needed {
+            end_writer.send(OnMatchEndEvent { winner: MatchWinner::Team(winner) });
+            return;
+        }
+    }
+
+    if rounds.round < rounds.best_of { return; }
+
+    let most_wins = rounds.wins.iter().map(|(_, wins)| *wins).max().unwrap_or(0);
+    let leaders = rounds.wins.iter().filter(|(_, wins)| *wins == most_wins).collect::<Vec<_>>();
+    let winner = match leaders.as_slice() {
+        [(team, _)] => MatchWinner::Team(*team),
+        _ => MatchWinner::Nobody,
+    };
+    end_writer.send(OnMatchEndEvent { winner });
+}
+
+pub fn remove_rounds(mut commands: Commands) {
+    commands.remove_resource::<RoundState>();
+}
@@ -0,0 +1,28 @@
+use bevy::app::{App, Plugin};
+use crate::simulation::server_sim::game_mode::{GameMode, GameModeRules, RespawnPolicy, score_by_kills, score_spawn_safety, spawn_safest};
+
+pub const FFA_NAME: &str = "ffa";
+
+/// Everyone against everyone, most kills when time runs out wins.
+pub struct FreeForAllMode;
+
+impl Plugin for FreeForAllMode {
+    fn build(&self, _app: &mut App) {}
+}
+
+impl GameMode for FreeForAllMode {
+    fn rules(&self) -> GameModeRules {
+        GameModeRules {
+            name: FFA_NAME,
+            display_name: "Free For All",
+            time_limit: Some(300.),
+            restart_delay: 10.,
+            respawn: RespawnPolicy::After(5.),
+            teams: 0,
+            select_spawn: spawn_safest,
+            score_spawn: score_spawn_safety,
+            score: score_by_kills,
+            extra_score: None,
+        }
+    }
+}
@@ -0,0 +1,136 @@
+mod systems;
+
+use bevy::app::App;
+use bevy::math::Vec2;
+use bevy::prelude::*;
+use bevy::utils::HashSet;
+use serde::{Deserialize, Serialize};
+use systems::*;
+use crate::AppState;
+use crate::ServerSet::ServerUpdate;
+use crate::simulation::PlayerData;
+use crate::simulation::server_sim::game_mode::{GameMode, GameModeRules, in_game_mode, is_match_over, RespawnPolicy, score_spawn_safety};
+use crate::simulation::server_sim::game_mode::teams::spawn_with_team;
+use crate::simulation::team::Team;
+
+pub const KING_OF_THE_HILL_NAME: &str = "koth";
+
+const SCORE_LIMIT: i32 = 300;
+const CONTROL_POINT_RADIUS: f32 = 300.;
+/// Seconds a single team needs to spend alone on a point to take it.
+const CAPTURE_TIME: f32 = 8.;
+/// How fast capture progress is lost while nobody is on the point.
+const DECAY_RATE: f32 = 0.5;
+
+/// Teams fight over control points, one in the middle of the map and one on the way to each base.
+/// Standing on a point your team holds scores a point per second, the first team to 300 wins.
+pub struct KingOfTheHillMode;
+
+impl GameMode for KingOfTheHillMode {
+    fn rules(&self) -> GameModeRules {
+        GameModeRules {
+            name: KING_OF_THE_HILL_NAME,
+            display_name: "King Of The Hill",
+            time_limit: Some(600.),
+            restart_delay: 10.,
+            respawn: RespawnPolicy::After(5.),
+            teams: 2,
+            select_spawn: spawn_with_team,
+            score_spawn: score_spawn_safety,
+            score: score_by_hill_time,
+            extra_score: Some("points"),
+        }
+    }
+}
+
+impl Plugin for KingOfTheHillMode {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(
+                (
+                    init_control_points.run_if(not(resource_exists::<ControlPoints>())),
+                    track_occupants.run_if(resource_exists::<ControlPoints>()),
+                    update_control.run_if(resource_exists::<ControlPoints>()),
+                    score_control_points.run_if(resource_exists::<ControlPoints>()),
+                    end_match_on_score_limit.run_if(not(is_match_over)),
+                ).chain().in_set(ServerUpdate).distributive_run_if(in_game_mode(KING_OF_THE_HILL_NAME))
+            )
+            .add_system(remove_control_points.in_schedule(OnExit(AppState::InGame)));
+    }
+}
+
+pub fn score_by_hill_time(data: &PlayerData) -> i32 {
+    data.hill_time as i32
+}
+
+/// Every control point's state, replicated to clients.
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ControlPoints {
+    pub points: Vec<ControlPoint>,
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ControlPoint {
+    pub name: String,
+    pub center: Vec2,
+    pub radius: f32,
+    pub owner: Option<Team>,
+    /// The team taking the point from its owner, and how far along they are from 0 to 1.
+    pub capturing: Option<Team>,
+    pub progress: f32,
+    /// More than one team is on the point, nothing moves until only one is left.
+    pub contested: bool,
+}
+
+impl ControlPoint {
+    pub fn new(name: String, center: Vec2) -> Self {
+        ControlPoint {
+            name,
+            center,
+            radius: CONTROL_POINT_RADIUS,
+            owner: None,
+            capturing: None,
+            progress: 0.,
+            contested: false,
+        }
+    }
+
+    /// Moves capture progress along for the teams standing on the point.
+    pub fn tick(&mut self, teams_inside: &HashSet<Team>, dt: f32) {
+        self.contested = teams_inside.len() > 1;
+        if self.contested { return; }
+
+        match teams_inside.iter().next().copied() {
+            None => {
+                self.progress = (self.progress - DECAY_RATE * dt / CAPTURE_TIME).max(0.);
+            }
+            Some(team) if self.owner == Some(team) => {
+                self.progress = (self.progress - dt / CAPTURE_TIME).max(0.);
+            }
+            Some(team) if self.capturing == Some(team) || self.progress <= 0. => {
+                self.capturing = Some(team);
+                self.progress += dt / CAPTURE_TIME;
+                if self.progress >= 1. {
+                    self.owner = Some(team);
+                    self.capturing = None;
+                    self.progress = 0.;
+                }
+            }
+            // Someone else's progress has to be pushed back to nothing first
+            Some(_) => {
+                self.progress = (self.progress - dt / CAPTURE_TIME).max(0.);
+            }
+        }
+
+        if self.progress <= 0. {
+            self.capturing = None;
+        }
+    }
+}
+
+/// The sensor for `ControlPoints::points[index]`, tracking the tanks inside it.
+#[derive(Component)]
+pub struct ControlPointSensor {
+    pub index: usize,
+    pub occupants: HashSet<Entity>,
+}
@@ -0,0 +1,131 @@
+use bevy::math::Vec2;
+use bevy::prelude::{Commands, EventReader, EventWriter, Query, Res, ResMut, Time, Transform, With};
+use bevy::utils::HashSet;
+use bevy_rapier2d::prelude::CollisionEvent;
+use crate::simulation::Lobby;
+use crate::simulation::events::OnPlayerDataChangedEvent;
+use crate::simulation::server_sim::game_mode::{ActiveGameMode, MatchWinner, OnMatchEndEvent};
+use crate::simulation::server_sim::game_mode::king_of_the_hill::{ControlPoint, ControlPoints, ControlPointSensor, SCORE_LIMIT, score_by_hill_time};
+use crate::simulation::server_sim::game_mode::teams::{rank_teams, team_spawn_points};
+use crate::simulation::server_sim::leveling::{ExperienceEvent, HILL_XP};
+use crate::simulation::server_sim::player::Player;
+use crate::simulation::server_sim::spawn::SpawnPoint;
+use crate::utils::prefabs::control_point_sensor;
+
+/// One point in the middle of the spawns, and one halfway from there to each team's spawns.
+pub fn init_control_points(
+    spawn_points: Query<&Transform, With<SpawnPoint>>,
+    mode: Res<ActiveGameMode>,
+    mut commands: Commands,
+) {
+    let spawn_points = spawn_points.iter()
+        .map(|trans| trans.translation.truncate())
+        .collect::<Vec<_>>();
+    if spawn_points.is_empty() { return; }
+
+    let center = spawn_points.iter().sum::<Vec2>() / spawn_points.len() as f32;
+    let positions = [center].into_iter().chain(mode.teams.iter().filter_map(|&team| {
+        let group = team_spawn_points(&spawn_points, &mode.teams, team)?;
+        let base = group.iter().sum::<Vec2>() / group.len() as f32;
+        Some(center.lerp(base, 0.5))
+    }));
+
+    let points = positions.zip('A'..).enumerate().map(|(index, (position, name))| {
+        let point = ControlPoint::new(name.to_string(), position);
+        commands.spawn(control_point_sensor(index, point.center, point.radius));
+        point
+    }).collect();
+
+    commands.insert_resource(ControlPoints { points });
+}
+
+pub fn track_occupants(
+    mut collision_events: EventReader<CollisionEvent>,
+    mut sensors: Query<&mut ControlPointSensor>,
+    players: Query<&Player>,
+) {
+    collision_events.iter().for_each(|e| {
+        let (ent1, ent2, entered) = match *e {
+            CollisionEvent::Started(ent1, ent2, _) => (ent1, ent2, true),
+            CollisionEvent::Stopped(ent1, ent2, _) => (ent1, ent2, false),
+        };
+
+        [(ent1, ent2), (ent2, ent1)].into_iter().for_each(|(sensor, player)| {
+            let Ok(mut sensor) = sensors.get_mut(sensor) else { return; };
+            if players.get(player).is_err() { return; }
+            if entered {
+                sensor.occupants.insert(player);
+            } else {
+                sensor.occupants.remove(&player);
+            }
+        });
+    });
+}
+
+pub fn update_control(
+    mut control_points: ResMut<ControlPoints>,
+    mut sensors: Query<&mut ControlPointSensor>,
+    players: Query<&Player>,
+    lobby: Res<Lobby>,
+    time: Res<Time>,
+) {
+    sensors.iter_mut().for_each(|mut sensor| {
+        // Tanks that died inside never send a collision event for leaving
+        sensor.occupants.retain(|&entity| players.get(entity).is_ok());
+
+        let teams_inside = sensor.occupants.iter()
+            .filter_map(|&entity| players.get(entity).ok())
+            .filter_map(|player| lobby.team_of(player.id))
+            .collect::<HashSet<_>>();
+
+        if let Some(point) = control_points.points.get_mut(sensor.index) {
+            point.tick(&teams_inside, time.delta_seconds());
+        }
+    });
+}
+
+/// Tanks on a point their team holds score for as long as nobody contests it, and earn
+/// experience for every second of it.
+pub fn score_control_points(
+    control_points: Res<ControlPoints>,
+    sensors: Query<&ControlPointSensor>,
+    players: Query<&Player>,
+    mut lobby: ResMut<Lobby>,
+    mut changed_writer: EventWriter<OnPlayerDataChangedEvent>,
+    mut xp_writer: EventWriter<ExperienceEvent>,
+    time: Res<Time>,
+) {
+    sensors.iter().for_each(|sensor| {
+        let Some(point) = control_points.points.get(sensor.index) else { return; };
+        let Some(owner) = point.owner else { return; };
+        if point.contested { return; }
+
+        sensor.occupants.iter().filter_map(|&entity| players.get(entity).ok()).for_each(|player| {
+            let Some(data) = lobby.player_data.get_mut(&player.id) else { return; };
+            if data.team != Some(owner) { return; }
+
+            let before = data.hill_time as i32;
+            data.hill_time += time.delta_seconds();
+            if data.hill_time as i32 != before {
+                changed_writer.send(OnPlayerDataChangedEvent { player_id: player.id });
+                xp_writer.send(ExperienceEvent { player_id: player.id, amount: HILL_XP });
+            }
+        });
+    });
+}
+
+pub fn end_match_on_score_limit(
+    lobby: Res<Lobby>,
+    mode: Res<ActiveGameMode>,
+    mut end_writer: EventWriter<OnMatchEndEvent>,
+) {
+    let Some(&(team, score)) = rank_teams(&lobby, &mode.teams, score_by_hill_time).first()
+        else { return; };
+    if score >= SCORE_LIMIT {
+        end_writer.send(OnMatchEndEvent { winner: MatchWinner::Team(team) });
+    }
+}
+
+pub fn remove_control_points(mut commands: Commands) {
+    commands.remove_resource::<ControlPoints>();
+}
@@ -0,0 +1,307 @@
+mod systems;
+pub mod ffa;
+pub mod battle_royale;
+pub mod capture_the_flag;
+pub mod elimination;
+pub mod king_of_the_hill;
+pub mod survival;
+pub mod team_deathmatch;
+pub mod teams;
+
+use bevy::app::App;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::ServerSet::ServerUpdate;
+use systems::*;
+use crate::AppState;
+use crate::simulation::{Lobby, PlayerData};
+use crate::simulation::server_sim::game_mode::battle_royale::BattleRoyaleMode;
+use crate::simulation::server_sim::game_mode::capture_the_flag::CaptureTheFlagMode;
+use crate::simulation::server_sim::game_mode::elimination::EliminationMode;
+use crate::simulation::server_sim::game_mode::king_of_the_hill::KingOfTheHillMode;
+use crate::simulation::server_sim::game_mode::ffa::FreeForAllMode;
+use crate::simulation::server_sim::game_mode::survival::SurvivalMode;
+use crate::simulation::server_sim::game_mode::team_deathmatch::TeamDeathmatchMode;
+use crate::simulation::server_sim::game_mode::teams::{assign_teams, choose_team, OnTeamChoiceEvent};
+use crate::simulation::server_sim::spawn::{lobby_players_on_connect, RECENT_COMBAT_TIME, spawn_player_system};
+use crate::simulation::server_sim::player::PlayerSystemStage::ApplyDamage;
+use crate::simulation::team::Team;
+use crate::utils::networking::is_server_listening;
+use crate::utils::networking::messages::PlayerId;
+
+/// Runs the match flow shared by every mode (time limit, scoring, end screen, restarts) and
+/// registers the built-in modes. The mode that's played is picked by `ServerConfig::mode`.
+pub struct GameModePlugin;
+
+impl Plugin for GameModePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .init_resource::<GameModeRegistry>()
+            .add_event::<OnMatchTimerFinishedEvent>()
+            .add_event::<OnMatchEndEvent>()
+            .add_event::<OnRestartMatchTimerFinishedEvent>()
+            .add_event::<OnTeamChoiceEvent>()
+            .add_system(init_match_on_enter
+                .run_if(is_server_listening)
+                .in_schedule(OnEnter(AppState::InGame)))
+            .add_systems(
+                (
+                    match_timer_clock.run_if(resource_exists::<MatchTimer>()),
+                    end_match_on_time_up.after(match_timer_clock),
+                    score_kills_and_deaths.after(ApplyDamage),
+                ).in_set(ServerUpdate).distributive_run_if(not(is_match_over))
+            )
+            .add_systems(
+                (
+                    assign_teams.after(lobby_players_on_connect),
+                    choose_team.after(assign_teams),
+                ).in_set(ServerUpdate).before(spawn_player_system)
+            )
+            .add_systems(
+                (
+                    pause_on_match_end,
+                    send_final_player_data,
+                    start_restart_timer_on_match_end,
+                    restart_timer_clock.run_if(is_restart_timer_ticking),
+                    new_match_on_restart_timer,
+                )
+            )
+            .add_systems(
+                (
+                    clear_player_scores_on_exit,
+                    remove_match_resources,
+                ).in_schedule(OnExit(AppState::InGame))
+            );
+
+        app
+            .add_game_mode(FreeForAllMode)
+            .add_game_mode(BattleRoyaleMode)
+            .add_game_mode(TeamDeathmatchMode)
+            .add_game_mode(CaptureTheFlagMode)
+            .add_game_mode(KingOfTheHillMode)
+            .add_game_mode(EliminationMode)
+            .add_game_mode(SurvivalMode);
+    }
+}
+
+/// A game mode is a plugin for its own systems plus the rules the shared match flow reads.
+/// Mode specific systems should be gated with `in_game_mode`.
+pub trait GameMode: Plugin {
+    fn rules(&self) -> GameModeRules;
+}
+
+pub trait GameModeAppExt {
+    fn add_game_mode<M: GameMode>(&mut self, mode: M) -> &mut Self;
+}
+
+impl GameModeAppExt for App {
+    fn add_game_mode<M: GameMode>(&mut self, mode: M) -> &mut Self {
+        self.init_resource::<GameModeRegistry>();
+        let rules = mode.rules();
+        self.world.resource_mut::<GameModeRegistry>().modes.push(rules);
+        self.add_plugin(mode)
+    }
+}
+
+#[derive(Clone)]
+pub struct GameModeRules {
+    /// Used to select the mode with `mode=<name>` and to announce it to clients.
+    pub name: &'static str,
+    pub display_name: &'static str,
+    /// Without a time limit the mode has to end the match itself with `OnMatchEndEvent`.
+    pub time_limit: Option<f32>,
+    pub restart_delay: f32,
+    pub respawn: RespawnPolicy,
+    /// How many teams players are split into, 0 for everyone playing for themselves.
+    pub teams: usize,
+    pub select_spawn: fn(&SpawnContext) -> Option<Vec2>,
+    /// Rates how safe a spawn point is for the player spawning, higher is safer.
+    pub score_spawn: fn(&SpawnContext, Vec2) -> f32,
+    /// Orders the leaderboard, and picks the winner when the time limit runs out.
+    pub score: fn(&PlayerData) -> i32,
+    /// What the score counts when it isn't kills, shown next to them.
+    pub extra_score: Option<&'static str>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RespawnPolicy {
+    After(f32),
+    Never,
+}
+
+pub struct SpawnContext<'a> {
+    pub player_id: PlayerId,
+    pub team: Option<Team>,
+    pub teams: Vec<Team>,
+    pub spawn_points: Vec<Vec2>,
+    pub players: Vec<(PlayerId, Option<Team>, Vec2)>,
+    /// Where tanks recently took damage or died, with how many seconds ago.
+    pub recent_combat: Vec<(Vec2, f32)>,
+    /// Whether the line between two points is clear of trees, obstacles and walls.
+    pub line_of_sight: &'a dyn Fn(Vec2, Vec2) -> bool,
+    /// The mode's `GameModeRules::score_spawn`.
+    pub score_spawn: fn(&SpawnContext, Vec2) -> f32,
+}
+
+impl SpawnContext<'_> {
+    /// Everyone else who isn't on the player's team.
+    pub fn enemies(&self) -> impl Iterator<Item = Vec2> + '_ {
+        self.players.iter()
+            .filter(|(id, team, _)| *id != self.player_id && (team.is_none() || *team != self.team))
+            .map(|(_, _, position)| *position)
+    }
+}
+
+/// Picks the spawn point the mode's `score_spawn` rates the safest.
+pub fn spawn_safest(context: &SpawnContext) -> Option<Vec2> {
+    safest_spawn_point(context, &context.spawn_points)
+}
+
+/// The best of `spawn_points` according to the mode's `score_spawn`.
+pub fn safest_spawn_point(context: &SpawnContext, spawn_points: &[Vec2]) -> Option<Vec2> {
+    spawn_points.iter().copied()
+        .map(|spawn| ((context.score_spawn)(context, spawn), spawn))
+        .max_by(|(x, _), (y, _)| x.total_cmp(y))
+        .map(|(_, spawn)| spawn)
+}
+
+/// Enemies further away than this don't make a spawn point any less safe.
+const SAFE_DISTANCE: f32 = 3000.;
+/// How much worse a spawn point is for every enemy that can see it, in units of distance.
+const VISIBLE_PENALTY: f32 = 1500.;
+/// How much worse a spawn point is for fighting that just happened next to it.
+const COMBAT_PENALTY: f32 = 1000.;
+const COMBAT_RADIUS: f32 = 1000.;
+
+/// Rates a spawn point by how far away the nearest enemy is, how many enemies have a clear shot
+/// at it and how much fighting happened nearby recently.
+pub fn score_spawn_safety(context: &SpawnContext, spawn: Vec2) -> f32 {
+    let nearby_enemies = context.enemies()
+        .filter(|enemy| enemy.distance(spawn) < SAFE_DISTANCE)
+        .collect::<Vec<_>>();
+
+    let nearest_enemy = nearby_enemies.iter()
+        .map(|enemy| enemy.distance(spawn))
+        .fold(SAFE_DISTANCE, f32::min);
+
+    let seen_by = nearby_enemies.iter()
+        .filter(|&&enemy| (context.line_of_sight)(spawn, enemy))
+        .count();
+
+    let combat = context.recent_combat.iter()
+        .filter(|(position, _)| position.distance(spawn) < COMBAT_RADIUS)
+        .map(|(_, age)| (1. - age / RECENT_COMBAT_TIME).max(0.))
+        .sum::<f32>();
+
+    nearest_enemy - seen_by as f32 * VISIBLE_PENALTY - combat * COMBAT_PENALTY
+}
+
+pub fn score_by_kills(data: &PlayerData) -> i32 {
+    data.kills as i32
+}
+
+/// Players sorted from the highest score down.
+pub fn rank_players(lobby: &Lobby, score: fn(&PlayerData) -> i32) -> Vec<(PlayerId, PlayerData)> {
+    let mut players = lobby.player_data.clone().into_iter().collect::<Vec<_>>();
+    players.sort_by_key(|(_, data)| -score(data));
+    players
+}
+
+#[derive(Resource, Default)]
+pub struct GameModeRegistry {
+    modes: Vec<GameModeRules>,
+}
+
+impl GameModeRegistry {
+    pub fn get(&self, name: &str) -> Option<&GameModeRules> {
+        self.modes.iter().find(|rules| rules.name == name)
+    }
+
+    pub fn names(&self) -> Vec<&'static str> {
+        self.modes.iter().map(|rules| rules.name).collect()
+    }
+}
+
+/// The mode being played. Servers pick it on entering the game, clients once it's announced.
+#[derive(Resource, Clone)]
+pub struct ActiveGameMode {
+    pub rules: GameModeRules,
+    /// The teams being played, empty when the mode has no teams.
+    pub teams: Vec<Team>,
+    /// Players joining while this is set wait for the next match instead of spawning.
+    pub spawns_locked: bool,
+}
+
+impl ActiveGameMode {
+    pub fn new(rules: GameModeRules) -> Self {
+        ActiveGameMode {
+            teams: Team::ALL.into_iter().take(rules.teams).collect(),
+            rules,
+            spawns_locked: false,
+        }
+    }
+}
+
+pub fn in_game_mode(name: &'static str) -> impl FnMut(Option<Res<ActiveGameMode>>) -> bool + Clone {
+    move |mode: Option<Res<ActiveGameMode>>| {
+        mode.map_or(false, |mode| mode.rules.name == name)
+    }
+}
+
+#[derive(Resource)]
+pub struct MatchTimer {
+    pub time_remaining: f32,
+    pub match_length: f32,
+}
+
+impl MatchTimer {
+    pub fn new(length: f32) -> Self {
+        Self {
+            match_length: length,
+            time_remaining: length,
+        }
+    }
+}
+
+#[derive(Resource)]
+pub struct RestartMatchTimer {
+    pub time_remaining: f32,
+}
+
+impl RestartMatchTimer {
+    pub fn new(length: f32) -> Self {
+        Self {
+            time_remaining: length
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum MatchWinner {
+    Player(PlayerId),
+    Team(Team),
+    Nobody,
+}
+
+/// Present once the match is over, on the server and on clients.
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MatchOutcome {
+    pub winner: MatchWinner,
+}
+
+pub struct OnMatchTimerFinishedEvent;
+
+pub struct OnMatchEndEvent {
+    pub winner: MatchWinner,
+}
+
+pub struct OnRestartMatchTimerFinishedEvent;
+
+pub fn is_match_over(outcome: Option<Res<MatchOutcome>>) -> bool {
+    outcome.is_some()
+}
+
+fn is_restart_timer_ticking(restart_timer: Option<Res<RestartMatchTimer>>) -> bool {
+    let Some(restart_timer) = restart_timer else { return false; };
+    restart_timer.time_remaining > 0.
+}
@@ -0,0 +1,111 @@
+mod systems;
+
+use bevy::app::App;
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use systems::*;
+use crate::AppState;
+use crate::ServerSet::ServerUpdate;
+use crate::simulation::server_sim::game_mode::{GameMode, GameModeRules, in_game_mode, is_match_over, RespawnPolicy, score_by_kills, score_spawn_safety};
+use crate::simulation::server_sim::game_mode::teams::spawn_with_team;
+use crate::simulation::server_sim::player::PlayerSystemStage::ApplyDamage;
+
+pub const SURVIVAL_NAME: &str = "coop";
+
+const WAVE_COUNT: u32 = 10;
+const STARTING_LIVES: u32 = 5;
+/// Clearing a wave earns the team another life.
+const LIVES_PER_WAVE: u32 = 1;
+const RESPAWN_DELAY: f32 = 5.;
+const WARMUP_LENGTH: f32 = 15.;
+const BREAK_LENGTH: f32 = 10.;
+/// How far outside the tree ring enemies are spawned.
+const ENEMY_SPAWN_DISTANCE: f32 = 400.;
+
+/// Everyone is on one team, defending against waves of AI tanks that come in from outside the map.
+/// Deaths spend the team's shared lives, and the match is lost once they run out with nobody left.
+pub struct SurvivalMode;
+
+impl GameMode for SurvivalMode {
+    fn rules(&self) -> GameModeRules {
+        GameModeRules {
+            name: SURVIVAL_NAME,
+            display_name: "Co-op Survival",
+            time_limit: None,
+            restart_delay: 10.,
+            // Respawns are paid for with lives, see `spend_lives`
+            respawn: RespawnPolicy::Never,
+            teams: 1,
+            select_spawn: spawn_with_team,
+            score_spawn: score_spawn_safety,
+            score: score_by_kills,
+            extra_score: None,
+        }
+    }
+}
+
+impl Plugin for SurvivalMode {
+    fn build(&self, app: &mut App) {
+        app
+            .add_systems(
+                (
+                    init_waves.run_if(not(resource_exists::<WaveState>())),
+                    count_enemies.run_if(resource_exists::<WaveState>()).after(ApplyDamage),
+                    wave_clock.run_if(resource_exists::<WaveState>()),
+                    spend_lives.run_if(resource_exists::<WaveState>()),
+                    end_match_on_wipe.run_if(resource_exists::<WaveState>()),
+                ).chain().in_set(ServerUpdate)
+                    .distributive_run_if(in_game_mode(SURVIVAL_NAME))
+                    .distributive_run_if(not(is_match_over))
+            )
+            .add_system(remove_waves.in_schedule(OnExit(AppState::InGame)));
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum WavePhase {
+    /// Counting down to the next wave, the first countdown is the warmup.
+    Break { time_remaining: f32 },
+    Fighting,
+}
+
+/// Progress through the waves, replicated to clients.
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WaveState {
+    /// Starts at 0 during the warmup.
+    pub wave: u32,
+    pub wave_count: u32,
+    pub phase: WavePhase,
+    pub enemies_remaining: u32,
+    /// Respawns left for the whole team.
+    pub lives: u32,
+}
+
+impl Default for WaveState {
+    fn default() -> Self {
+        WaveState {
+            wave: 0,
+            wave_count: WAVE_COUNT,
+            phase: WavePhase::Break { time_remaining: WARMUP_LENGTH },
+            enemies_remaining: 0,
+            lives: STARTING_LIVES,
+        }
+    }
+}
+
+/// How many enemies a wave sends in, and how tough they are.
+pub struct WaveSpec {
+    pub enemies: u32,
+    pub health: f32,
+    pub fire_interval: f32,
+}
+
+impl WaveSpec {
+    pub fn for_wave(wave: u32) -> Self {
+        WaveSpec {
+            enemies: 2 + wave * 2,
+            health: 40. + wave as f32 * 10.,
+            fire_interval: (1.5 - wave as f32 * 0.1).max(0.5),
+        }
+    }
+}
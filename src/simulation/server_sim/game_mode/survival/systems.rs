@@ -0,0 +1,126 @@
+use bevy::hierarchy::BuildChildren;
+use bevy::math::Vec2;
+use bevy::prelude::{Commands, EventReader, EventWriter, Query, Res, ResMut, Time, Without, With};
+use rand::seq::SliceRandom;
+use crate::simulation::{Lobby, Object, SyncedObjects};
+use crate::simulation::events::OnPlayerSpawnEvent;
+use crate::simulation::map::MapData;
+use crate::simulation::server_sim::ai::{AiTank, next_ai_player_id};
+use crate::simulation::server_sim::game_mode::{ActiveGameMode, MatchWinner, OnMatchEndEvent};
+use crate::simulation::server_sim::game_mode::survival::{BREAK_LENGTH, ENEMY_SPAWN_DISTANCE, LIVES_PER_WAVE, RESPAWN_DELAY, WavePhase, WaveSpec, WaveState};
+use crate::simulation::server_sim::player::{Health, OnPlayerDeathEvent, Player};
+use crate::simulation::server_sim::respawn::RespawnTimer;
+use crate::simulation::server_sim::weapon::DEFAULT_WEAPON;
+use crate::utils::generate_evenly_spaced_points_on_polygon_edges;
+use crate::utils::prefabs::{get_ai_tank_bundle, get_player_bundle, get_turret_bundle, TANK_COLLIDER_RADIUS};
+
+pub fn init_waves(mut commands: Commands) {
+    commands.insert_resource(WaveState::default());
+}
+
+/// Runs before `wave_clock`, so a wave that was just sent in is counted once it has spawned.
+pub fn count_enemies(
+    mut waves: ResMut<WaveState>,
+    enemies: Query<&Health, With<AiTank>>,
+    mode: Res<ActiveGameMode>,
+    mut end_writer: EventWriter<OnMatchEndEvent>,
+) {
+    if waves.phase != WavePhase::Fighting { return; }
+
+    // Dead tanks are only despawned at the end of the frame, so go by health
+    waves.enemies_remaining = enemies.iter().filter(|health| health.health > 0.).count() as u32;
+    if waves.enemies_remaining > 0 { return; }
+
+    if waves.wave >= waves.wave_count {
+        let winner = mode.teams.first().map_or(MatchWinner::Nobody, |&team| MatchWinner::Team(team));
+        end_writer.send(OnMatchEndEvent { winner });
+        return;
+    }
+
+    waves.lives += LIVES_PER_WAVE;
+    waves.phase = WavePhase::Break { time_remaining: BREAK_LENGTH };
+}
+
+pub fn wave_clock(
+    mut waves: ResMut<WaveState>,
+    lobby: Res<Lobby>,
+    map: Option<Res<MapData>>,
+    mut objects: ResMut<SyncedObjects>,
+    mut spawn_writer: EventWriter<OnPlayerSpawnEvent>,
+    mut commands: Commands,
+    time: Res<Time>,
+) {
+    let WavePhase::Break { time_remaining } = &mut waves.phase else { return; };
+    // Nobody to defend against
+    if lobby.player_data.is_empty() { return; }
+
+    *time_remaining -= time.delta_seconds();
+    if *time_remaining > 0. { return; }
+
+    let Some(map) = map else { return; };
+    waves.wave += 1;
+    let spec = WaveSpec::for_wave(waves.wave);
+
+    let center = map.bounds.iter().sum::<Vec2>() / map.bounds.len().max(1) as f32;
+    let edge_points = generate_evenly_spaced_points_on_polygon_edges(&map.bounds, TANK_COLLIDER_RADIUS * 4.);
+    let mut rng = rand::thread_rng();
+
+    (0..spec.enemies).filter_map(|_| edge_points.choose(&mut rng)).for_each(|&edge| {
+        let position = edge + (edge - center).normalize_or_zero() * ENEMY_SPAWN_DISTANCE;
+        let player_id = next_ai_player_id();
+        let object = Object::new();
+
+        let entity = commands.spawn(get_player_bundle(player_id, Some(position)))
+            .insert((get_ai_tank_bundle(spec.health, spec.fire_interval), object))
+            .with_children(|p| {
+                p.spawn(get_turret_bundle(DEFAULT_WEAPON));
+            }).id();
+        objects.objects.insert(object.id, entity);
+
+        spawn_writer.send(OnPlayerSpawnEvent {
+            player_id,
+            object_id: object.id,
+            position,
+        });
+    });
+
+    waves.enemies_remaining = spec.enemies;
+    waves.phase = WavePhase::Fighting;
+}
+
+/// Respawns go through the usual timer, as long as the team has lives left to pay for them.
+pub fn spend_lives(
+    mut death_events: EventReader<OnPlayerDeathEvent>,
+    mut waves: ResMut<WaveState>,
+    mut respawn_timer: ResMut<RespawnTimer>,
+    lobby: Res<Lobby>,
+) {
+    death_events.iter()
+        .filter(|e| lobby.player_data.contains_key(&e.player_id))
+        .for_each(|e| {
+            if waves.lives == 0 { return; }
+            waves.lives -= 1;
+            respawn_timer.map.insert(e.player_id, RESPAWN_DELAY);
+        });
+}
+
+/// The match is lost once the lives are gone and so is every player's tank.
+pub fn end_match_on_wipe(
+    waves: Res<WaveState>,
+    players: Query<(&Player, &Health), Without<AiTank>>,
+    respawn_timer: Res<RespawnTimer>,
+    lobby: Res<Lobby>,
+    mut end_writer: EventWriter<OnMatchEndEvent>,
+) {
+    if waves.lives > 0 || lobby.player_data.is_empty() || !respawn_timer.map.is_empty() { return; }
+
+    let anyone_alive = players.iter()
+        .any(|(player, health)| health.health > 0. && lobby.player_data.contains_key(&player.id));
+    if !anyone_alive {
+        end_writer.send(OnMatchEndEvent { winner: MatchWinner::Nobody });
+    }
+}
+
+pub fn remove_waves(mut commands: Commands) {
+    commands.remove_resource::<WaveState>();
+}
@@ -0,0 +1,191 @@
+use bevy::log::{info, warn};
+use bevy::prelude::{Commands, default, EventReader, EventWriter, NextState, Res, ResMut, Time};
+use crate::AppState;
+use crate::simulation::{Lobby, PlayerData};
+use crate::simulation::events::OnPlayerDataChangedEvent;
+use crate::simulation::server_sim::config::ServerConfig;
+use crate::simulation::server_sim::game_mode::{ActiveGameMode, rank_players, GameModeRegistry, MatchOutcome, MatchTimer, MatchWinner, OnMatchEndEvent, OnMatchTimerFinishedEvent, OnRestartMatchTimerFinishedEvent, RestartMatchTimer};
+use crate::simulation::server_sim::game_mode::ffa::FFA_NAME;
+use crate::simulation::server_sim::game_mode::teams::rank_teams;
+use crate::simulation::server_sim::InGameState;
+use crate::simulation::server_sim::player::{OnAssistEvent, OnDamageDealtEvent, OnKillEvent, OnPlayerDeathEvent};
+use crate::simulation::team::Team;
+
+pub fn init_match_on_enter(
+    mut commands: Commands,
+    config: Res<ServerConfig>,
+    registry: Res<GameModeRegistry>,
+) {
+    let mut rules = match registry.get(&config.mode) {
+        Some(rules) => rules.clone(),
+        None => {
+            warn!("Unknown mode '{}', expected one of {:?}. Playing free for all",
+                config.mode, registry.names());
+            registry.get(FFA_NAME).unwrap().clone()
+        }
+    };
+
+    if rules.teams > 0 && let Some(teams) = config.teams {
+        rules.teams = teams.clamp(2, Team::ALL.len());
+    }
+
+    info!("Starting a {} match", rules.display_name);
+    if let Some(time_limit) = rules.time_limit {
+        commands.insert_resource(MatchTimer::new(time_limit));
+    }
+    commands.insert_resource(ActiveGameMode::new(rules));
+}
+
+pub fn match_timer_clock(
+    mut match_timer: ResMut<MatchTimer>,
+    time: Res<Time>,
+    mut finished_writer: EventWriter<OnMatchTimerFinishedEvent>
+) {
+    if match_timer.time_remaining <= 0. { return; }
+
+    match_timer.time_remaining -= time.delta_seconds();
+    if match_timer.time_remaining <= 0. {
+        finished_writer.send(OnMatchTimerFinishedEvent);
+    }
+}
+
+/// The highest score wins once time is up, a tie means nobody does. In team modes the team scores
+/// are compared instead.
+pub fn end_match_on_time_up(
+    mut events: EventReader<OnMatchTimerFinishedEvent>,
+    mut end_writer: EventWriter<OnMatchEndEvent>,
+    mode: Res<ActiveGameMode>,
+    lobby: Res<Lobby>,
+) {
+    if events.iter().next().is_none() { return; }
+
+    if !mode.teams.is_empty() {
+        let winner = match rank_teams(&lobby, &mode.teams, mode.rules.score).as_slice() {
+            [(team, first), (_, second), ..] if first > second => MatchWinner::Team(*team),
+            _ => MatchWinner::Nobody,
+        };
+        end_writer.send(OnMatchEndEvent { winner });
+        return;
+    }
+
+    let scores = rank_players(&lobby, mode.rules.score).into_iter()
+        .map(|(id, data)| (id, (mode.rules.score)(&data)))
+        .collect::<Vec<_>>();
+
+    let winner = match scores.as_slice() {
+        [(id, _)] => MatchWinner::Player(*id),
+        [(id, first), (_, second), ..] if first > second => MatchWinner::Player(*id),
+        _ => MatchWinner::Nobody,
+    };
+    end_writer.send(OnMatchEndEvent { winner });
+}
+
+pub fn score_kills_and_deaths(
+    mut kill_events: EventReader<OnKillEvent>,
+    mut assist_events: EventReader<OnAssistEvent>,
+    mut death_events: EventReader<OnPlayerDeathEvent>,
+    mut dealt_events: EventReader<OnDamageDealtEvent>,
+    mut lobby: ResMut<Lobby>,
+) {
+    kill_events.iter().for_each(|e| {
+        if let Some(attacker_data) = lobby.player_data.get_mut(&e.attacker_id) {
+            attacker_data.kills += 1;
+        }
+    });
+
+    assist_events.iter().for_each(|e| {
+        if let Some(assister_data) = lobby.player_data.get_mut(&e.assister_id) {
+            assister_data.assists += 1;
+        }
+    });
+
+    dealt_events.iter().for_each(|e| {
+        if let Some(attacker_data) = lobby.player_data.get_mut(&e.attacker_id) {
+            attacker_data.damage_dealt += e.damage;
+        }
+    });
+
+    death_events.iter().for_each(|e| {
+        if let Some(victim_data) = lobby.player_data.get_mut(&e.player_id) {
+            victim_data.deaths += 1;
+        }
+    });
+}
+
+pub fn pause_on_match_end(
+    mut events: EventReader<OnMatchEndEvent>,
+    mut next_state: ResMut<NextState<InGameState>>,
+    mut commands: Commands,
+) {
+    events.iter().for_each(|e| {
+        commands.insert_resource(MatchOutcome { winner: e.winner.clone() });
+        next_state.set(InGameState::Paused);
+    });
+}
+
+/// Damage dealt isn't sent on every hit, so everyone's data is sent once more for the end screen.
+pub fn send_final_player_data(
+    mut events: EventReader<OnMatchEndEvent>,
+    lobby: Res<Lobby>,
+    mut changed_writer: EventWriter<OnPlayerDataChangedEvent>,
+) {
+    if events.iter().next().is_none() { return; }
+
+    lobby.player_data.keys().for_each(|&player_id| {
+        changed_writer.send(OnPlayerDataChangedEvent { player_id });
+    });
+}
+
+pub fn start_restart_timer_on_match_end(
+    mut events: EventReader<OnMatchEndEvent>,
+    mode: Option<Res<ActiveGameMode>>,
+    mut commands: Commands
+) {
+    let Some(mode) = mode else { return; };
+    events.iter().for_each(|_| {
+        commands.insert_resource(RestartMatchTimer::new(mode.rules.restart_delay));
+    });
+}
+
+pub fn restart_timer_clock(
+    mut restart_timer: ResMut<RestartMatchTimer>,
+    time: Res<Time>,
+    mut finished_writer: EventWriter<OnRestartMatchTimerFinishedEvent>
+) {
+    restart_timer.time_remaining -= time.delta_seconds();
+    if restart_timer.time_remaining <= 0. {
+        finished_writer.send(OnRestartMatchTimerFinishedEvent);
+    }
+}
+
+pub fn new_match_on_restart_timer(
+    mut events: EventReader<OnRestartMatchTimerFinishedEvent>,
+    mut next_in_game_state: ResMut<NextState<InGameState>>,
+    mut next_app_state: ResMut<NextState<AppState>>,
+) {
+    events.iter().for_each(|_| {
+        next_in_game_state.set(InGameState::Playing);
+        next_app_state.set(AppState::InGame);
+    });
+}
+
+/// Keeps each player's tank, team and weapon pick, everything else in `PlayerData` only counts
+/// for the match that ended.
+pub fn clear_player_scores_on_exit(
+    mut lobby: ResMut<Lobby>,
+) {
+    lobby.player_data.values_mut().for_each(|data| {
+        *data = PlayerData {
+            object_id: data.object_id,
+            team: data.team,
+            weapon: data.weapon.take(),
+            ..default()
+        };
+    });
+}
+
+pub fn remove_match_resources(mut commands: Commands) {
+    commands.remove_resource::<MatchTimer>();
+    commands.remove_resource::<RestartMatchTimer>();
+    commands.remove_resource::<MatchOutcome>();
+}
@@ -0,0 +1,29 @@
+use bevy::app::{App, Plugin};
+use crate::simulation::server_sim::game_mode::{GameMode, GameModeRules, RespawnPolicy, score_by_kills, score_spawn_safety};
+use crate::simulation::server_sim::game_mode::teams::spawn_with_team;
+
+pub const TEAM_DEATHMATCH_NAME: &str = "tdm";
+
+/// Free for all split into teams, the team with the most kills when time runs out wins.
+pub struct TeamDeathmatchMode;
+
+impl Plugin for TeamDeathmatchMode {
+    fn build(&self, _app: &mut App) {}
+}
+
+impl GameMode for TeamDeathmatchMode {
+    fn rules(&self) -> GameModeRules {
+        GameModeRules {
+            name: TEAM_DEATHMATCH_NAME,
+            display_name: "Team Deathmatch",
+            time_limit: Some(300.),
+            restart_delay: 10.,
+            respawn: RespawnPolicy::After(5.),
+            teams: 2,
+            select_spawn: spawn_with_team,
+            score_spawn: score_spawn_safety,
+            score: score_by_kills,
+            extra_score: None,
+        }
+    }
+}
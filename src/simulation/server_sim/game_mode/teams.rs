@@ -0,0 +1,109 @@
+use bevy::log::info;
+use bevy::math::Vec2;
+use bevy::prelude::{EventReader, EventWriter, Res, ResMut};
+use crate::simulation::{Lobby, PlayerData};
+use crate::simulation::events::OnPlayerDataChangedEvent;
+use crate::simulation::server_sim::game_mode::{ActiveGameMode, safest_spawn_point, SpawnContext, spawn_safest};
+use crate::simulation::team::Team;
+use crate::utils::networking::messages::PlayerId;
+
+/// Sent by a client picking a team for themselves.
+pub struct OnTeamChoiceEvent {
+    pub player_id: PlayerId,
+    pub team: Team,
+}
+
+pub fn team_size(lobby: &Lobby, team: Team) -> usize {
+    lobby.player_data.values().filter(|data| data.team == Some(team)).count()
+}
+
+/// The team with the fewest players, ties go to the first listed team.
+pub fn smallest_team(lobby: &Lobby, teams: &[Team]) -> Option<Team> {
+    teams.iter().copied().min_by_key(|&team| team_size(lobby, team))
+}
+
+/// Puts every player without a team in the smallest one.
+pub fn assign_teams(
+    mode: Option<Res<ActiveGameMode>>,
+    mut lobby: ResMut<Lobby>,
+    mut changed_writer: EventWriter<OnPlayerDataChangedEvent>,
+) {
+    let Some(mode) = mode else { return; };
+    if mode.teams.is_empty() { return; }
+
+    let mut unassigned = lobby.player_data.iter()
+        .filter(|(_, data)| !data.team.map_or(false, |team| mode.teams.contains(&team)))
+        .map(|(&id, _)| id)
+        .collect::<Vec<_>>();
+    unassigned.sort();
+
+    unassigned.into_iter().for_each(|player_id| {
+        let team = smallest_team(&lobby, &mode.teams);
+        if let Some(data) = lobby.player_data.get_mut(&player_id) {
+            data.team = team;
+            changed_writer.send(OnPlayerDataChangedEvent { player_id });
+        }
+    });
+}
+
+/// Players may only move to a team that's smaller than their own, so teams stay balanced.
+pub fn choose_team(
+    mut choice_events: EventReader<OnTeamChoiceEvent>,
+    mode: Option<Res<ActiveGameMode>>,
+    mut lobby: ResMut<Lobby>,
+    mut changed_writer: EventWriter<OnPlayerDataChangedEvent>,
+) {
+    let Some(mode) = mode else { return; };
+
+    choice_events.iter().for_each(|e| {
+        if !mode.teams.contains(&e.team) { return; }
+        let Some(current) = lobby.team_of(e.player_id) else { return; };
+        if current == e.team || team_size(&lobby, e.team) >= team_size(&lobby, current) { return; }
+
+        if let Some(data) = lobby.player_data.get_mut(&e.player_id) {
+            info!("Player {} joined the {} team", e.player_id, e.team.name());
+            data.team = Some(e.team);
+            changed_writer.send(OnPlayerDataChangedEvent { player_id: e.player_id });
+        }
+    });
+}
+
+/// Spawn points are split into one group per team by their angle around the middle of the map.
+pub fn team_spawn_points(spawn_points: &[Vec2], teams: &[Team], team: Team) -> Option<Vec<Vec2>> {
+    let index = teams.iter().position(|&other| other == team)?;
+    if spawn_points.is_empty() { return None; }
+
+    let center = spawn_points.iter().sum::<Vec2>() / spawn_points.len() as f32;
+    let angle = |point: &Vec2| {
+        let offset = *point - center;
+        offset.y.atan2(offset.x)
+    };
+    let mut spawn_points = spawn_points.to_vec();
+    spawn_points.sort_by(|a, b| angle(a).total_cmp(&angle(b)));
+
+    let group_size = (spawn_points.len() + teams.len() - 1) / teams.len();
+    spawn_points.chunks(group_size).nth(index).map(<[Vec2]>::to_vec)
+}
+
+/// Players spawn at the safest of their team's group of spawn points.
+pub fn spawn_with_team(context: &SpawnContext) -> Option<Vec2> {
+    let team_group = context.team
+        .and_then(|team| team_spawn_points(&context.spawn_points, &context.teams, team));
+
+    match team_group {
+        Some(spawn_points) => safest_spawn_point(context, &spawn_points),
+        None => spawn_safest(context),
+    }
+}
+
+/// Teams sorted from the highest combined score of their players down.
+pub fn rank_teams(lobby: &Lobby, teams: &[Team], score: fn(&PlayerData) -> i32) -> Vec<(Team, i32)> {
+    let mut scores = teams.iter().map(|&team| {
+        (team, lobby.player_data.values()
+            .filter(|data| data.team == Some(team))
+            .map(score)
+            .sum::<i32>())
+    }).collect::<Vec<_>>();
+    scores.sort_by_key(|(_, score)| -score);
+    scores
+}
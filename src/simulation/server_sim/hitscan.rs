@@ -0,0 +1,192 @@
+use std::cmp::Ordering;
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::{QueryFilter, RapierContext};
+use crate::ServerSet::ServerUpdate;
+use crate::simulation::Lobby;
+use crate::simulation::map::MapEntity;
+use crate::simulation::server_sim::ai::AiTank;
+use crate::simulation::server_sim::bullet::BulletSystemStage::CollisionHandle;
+use crate::simulation::server_sim::bullet::is_friendly_fire;
+use crate::simulation::server_sim::player::{OnDamageEvent, Player};
+use crate::simulation::server_sim::weapon::HitscanDefinition;
+
+pub struct HitscanPlugin;
+
+impl Plugin for HitscanPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_event::<HitscanEvent>()
+            .add_event::<OnBeamEvent>()
+            .add_system(resolve_hitscan.in_set(CollisionHandle).in_set(ServerUpdate));
+    }
+}
+
+/// A hitscan weapon was fired, resolved in the same frame.
+pub struct HitscanEvent {
+    pub shooter: Entity,
+    /// Where the ray is cast from, the turret's center.
+    pub origin: Vec2,
+    /// Where the beam is drawn from, the end of the barrel.
+    pub muzzle: Vec2,
+    pub direction: Vec2,
+    pub damage: f32,
+    pub hitscan: HitscanDefinition,
+}
+
+/// Sent to clients so they can draw the beam.
+pub struct OnBeamEvent {
+    pub start: Vec2,
+    pub end: Vec2,
+}
+
+/// Casts every shot against the physics world, damaging the tanks along the ray up to the
+/// first tree or wall. Non-piercing shots stop at the first tank, friendly or not.
+fn resolve_hitscan(
+    mut hitscan_events: EventReader<HitscanEvent>,
+    rapier_context: Res<RapierContext>,
+    obstacles: Query<(), With<MapEntity>>,
+    players: Query<(&Player, Option<&AiTank>)>,
+    lobby: Res<Lobby>,
+    mut damage_writer: EventWriter<OnDamageEvent>,
+    mut beam_writer: EventWriter<OnBeamEvent>,
+) {
+    hitscan_events.iter().for_each(|e| {
+        let filter = QueryFilter::new().exclude_sensors().exclude_collider(e.shooter);
+
+        let mut hits = Vec::new();
+        rapier_context.intersections_with_ray(
+            e.origin, e.direction, e.hitscan.range, true, filter,
+            |entity, intersection| {
+                hits.push((entity, intersection.toi));
+                true
+            });
+        hits.sort_by(|(_, a), (_, b)| a.partial_cmp(b).unwrap_or(Ordering::Equal));
+
+        let mut distance = e.hitscan.range;
+        for (entity, toi) in hits {
+            if obstacles.get(entity).is_ok() {
+                distance = toi;
+                break;
+            }
+            if players.get(entity).is_err() { continue; }
+
+            if !is_friendly_fire(Some(e.shooter), entity, &players, &lobby) {
+                damage_writer.send(OnDamageEvent {
+                    victim: entity,
+                    attacker: Some(e.shooter),
+                    damage: e.damage,
+                });
+            }
+            if !e.hitscan.pierce {
+                distance = toi;
+                break;
+            }
+        }
+
+        beam_writer.send(OnBeamEvent {
+            start: e.muzzle,
+            end: e.origin + e.direction * distance,
+        });
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy_rapier2d::prelude::{Collider, NoUserData, RapierPhysicsPlugin};
+    use crate::utils::prefabs::TANK_COLLIDER_RADIUS;
+    use super::*;
+
+    const TREE_RADIUS: f32 = 20.;
+
+    fn hitscan_app() -> App {
+        let mut app = App::new();
+        app
+            .add_plugins(MinimalPlugins)
+            .add_plugin(TransformPlugin)
+            .add_plugin(HierarchyPlugin)
+            .add_plugin(RapierPhysicsPlugin::<NoUserData>::pixels_per_meter(150.))
+            .init_resource::<Lobby>()
+            .add_event::<HitscanEvent>()
+            .add_event::<OnBeamEvent>()
+            .add_event::<OnDamageEvent>()
+            .add_system(resolve_hitscan);
+        app
+    }
+
+    fn spawn_tank(app: &mut App, id: u64, x: f32) -> Entity {
+        app.world.spawn((
+            Player::new(id),
+            Collider::ball(TANK_COLLIDER_RADIUS),
+            TransformBundle::from_transform(Transform::from_xyz(x, 0., 0.)),
+        )).id()
+    }
+
+    fn spawn_tree(app: &mut App, x: f32) {
+        app.world.spawn((
+            MapEntity,
+            Collider::ball(TREE_RADIUS),
+            TransformBundle::from_transform(Transform::from_xyz(x, 0., 0.)),
+        ));
+    }
+
+    /// Fires along the x axis from the shooter's center, returning the tanks that were damaged
+    /// and how far the beam went.
+    fn fire(app: &mut App, shooter: Entity, pierce: bool) -> (Vec<Entity>, f32) {
+        // Lets rapier pick up the colliders first
+        app.update();
+        app.world.send_event(HitscanEvent {
+            shooter,
+            origin: Vec2::ZERO,
+            muzzle: Vec2::ZERO,
+            direction: Vec2::X,
+            damage: 10.,
+            hitscan: HitscanDefinition { range: 1000., pierce },
+        });
+        app.update();
+
+        let damage_events = app.world.resource::<Events<OnDamageEvent>>();
+        let mut victims = damage_events.get_reader().iter(damage_events).map(|e| e.victim).collect::<Vec<_>>();
+        victims.sort();
+        let beam_events = app.world.resource::<Events<OnBeamEvent>>();
+        let beam = beam_events.get_reader().iter(beam_events).next().expect("every shot draws a beam");
+        (victims, beam.end.x)
+    }
+
+    #[test]
+    fn stops_at_map_entities() {
+        let mut app = hitscan_app();
+        let shooter = spawn_tank(&mut app, 1, 0.);
+        spawn_tree(&mut app, 300.);
+        spawn_tank(&mut app, 2, 600.);
+
+        let (victims, distance) = fire(&mut app, shooter, true);
+        assert!(victims.is_empty());
+        assert!((distance - (300. - TREE_RADIUS)).abs() < 0.1, "beam went {distance}");
+    }
+
+    #[test]
+    fn piercing_shots_hit_every_tank_in_line() {
+        let mut app = hitscan_app();
+        let shooter = spawn_tank(&mut app, 1, 0.);
+        let first = spawn_tank(&mut app, 2, 300.);
+        let second = spawn_tank(&mut app, 3, 600.);
+
+        let (victims, distance) = fire(&mut app, shooter, true);
+        let mut expected = vec![first, second];
+        expected.sort();
+        assert_eq!(victims, expected);
+        assert_eq!(distance, 1000.);
+    }
+
+    #[test]
+    fn other_shots_stop_at_the_first_tank() {
+        let mut app = hitscan_app();
+        let shooter = spawn_tank(&mut app, 1, 0.);
+        let first = spawn_tank(&mut app, 2, 300.);
+        spawn_tank(&mut app, 3, 600.);
+
+        let (victims, distance) = fire(&mut app, shooter, false);
+        assert_eq!(victims, vec![first]);
+        assert!((distance - (300. - TANK_COLLIDER_RADIUS)).abs() < 0.1, "beam went {distance}");
+    }
+}
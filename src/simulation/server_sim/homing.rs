@@ -0,0 +1,163 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::Velocity;
+use crate::simulation::Lobby;
+use crate::simulation::server_sim::ai::AiTank;
+use crate::simulation::server_sim::bullet::{Bullet, is_friendly_fire};
+use crate::simulation::server_sim::player::Player;
+use crate::simulation::server_sim::weapon::HomingDefinition;
+
+/// Steers a bullet towards its target, turning no faster than `turn_rate`.
+#[derive(Component)]
+pub struct Homing {
+    /// Where the turret was aiming when the bullet was fired, targets are picked around it.
+    pub aim: Vec2,
+    /// Radians either side of the aim.
+    pub lock_angle: f32,
+    pub lock_range: f32,
+    /// Radians per second.
+    pub turn_rate: f32,
+    pub target: Option<Entity>,
+}
+
+impl Homing {
+    pub fn new(definition: HomingDefinition, aim: Vec2) -> Self {
+        Homing {
+            aim,
+            lock_angle: definition.lock_angle.to_radians(),
+            lock_range: definition.lock_range,
+            turn_rate: definition.turn_rate.to_radians(),
+            target: None,
+        }
+    }
+}
+
+/// Locks homing bullets onto the nearest enemy within their cone, which is the turret's aim
+/// when they're fired rather than their heading, so spread and turning don't move it, and turns
+/// them towards it. Bullets that lose their target look for another one. The new heading is
+/// replicated with the bullet's transform.
+pub fn steer_homing_bullets(
+    mut bullets: Query<(&mut Homing, &Bullet, &mut Transform, &mut Velocity)>,
+    targets: Query<(Entity, &Transform), (With<Player>, Without<Bullet>)>,
+    players: Query<(&Player, Option<&AiTank>)>,
+    lobby: Res<Lobby>,
+    time: Res<Time>,
+) {
+    bullets.iter_mut().for_each(|(mut homing, bullet, mut trans, mut vel)| {
+        if bullet.lifetime <= 0. { return; }
+
+        let position = trans.translation.truncate();
+        let heading = vel.linvel.normalize_or_zero();
+        if heading == Vec2::ZERO { return; }
+
+        if homing.target.map_or(true, |target| targets.get(target).is_err()) {
+            homing.target = targets.iter()
+                .filter(|&(entity, _)| bullet.owner != Some(entity) &&
+                    !is_friendly_fire(bullet.owner, entity, &players, &lobby))
+                .map(|(entity, target_trans)| (entity, target_trans.translation.truncate() - position))
+                .filter(|(_, offset)| offset.length() <= homing.lock_range &&
+                    homing.aim.angle_between(*offset).abs() <= homing.lock_angle)
+                .min_by(|(_, a), (_, b)| a.length().total_cmp(&b.length()))
+                .map(|(entity, _)| entity);
+        }
+
+        let Some(Ok((_, target_trans))) = homing.target.map(|target| targets.get(target)) else { return; };
+
+        let wanted = target_trans.translation.truncate() - position;
+        if wanted == Vec2::ZERO { return; }
+        let max_turn = homing.turn_rate * time.delta_seconds();
+        let turn = heading.angle_between(wanted).clamp(-max_turn, max_turn);
+        let new_heading = Vec2::from_angle(turn).rotate(heading);
+
+        vel.linvel = new_heading * vel.linvel.length();
+        trans.rotation = Quat::from_axis_angle(Vec3::Z, new_heading.y.atan2(new_heading.x));
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use bevy::utils::{Duration, Instant};
+    use super::*;
+
+    const DT: f32 = 0.1;
+    const HOMING: HomingDefinition = HomingDefinition {
+        lock_angle: 30.,
+        lock_range: 1000.,
+        turn_rate: 90.,
+    };
+
+    /// Every update is `DT` seconds long.
+    fn homing_app() -> App {
+        let mut time = Time::default();
+        let start = Instant::now();
+        time.update_with_instant(start);
+        time.update_with_instant(start + Duration::from_secs_f32(DT));
+
+        let mut app = App::new();
+        app
+            .init_resource::<Lobby>()
+            .insert_resource(time)
+            .add_system(steer_homing_bullets);
+        app
+    }
+
+    fn spawn_bullet(app: &mut App, heading: Vec2, aim: Vec2) -> Entity {
+        app.world.spawn((
+            Homing::new(HOMING, aim),
+            Bullet {
+                owner: None,
+                lifetime: 1.,
+                damage: 10.,
+                radius: 10.,
+                bounces_left: 0,
+                bounce_speed_loss: 0.,
+                explosion: None,
+            },
+            Transform::default(),
+            Velocity::linear(heading * 100.),
+        )).id()
+    }
+
+    fn spawn_target(app: &mut App, id: u64, degrees: f32, distance: f32) -> Entity {
+        let position = Vec2::from_angle(degrees.to_radians()) * distance;
+        app.world.spawn((Player::new(id), Transform::from_translation(position.extend(0.)))).id()
+    }
+
+    fn target_of(app: &App, bullet: Entity) -> Option<Entity> {
+        app.world.get::<Homing>(bullet).unwrap().target
+    }
+
+    #[test]
+    fn only_locks_on_within_the_cone() {
+        let mut app = homing_app();
+        let bullet = spawn_bullet(&mut app, Vec2::X, Vec2::X);
+        spawn_target(&mut app, 1, 60., 200.);
+        let inside = spawn_target(&mut app, 2, -20., 400.);
+
+        app.update();
+        assert_eq!(target_of(&app, bullet), Some(inside));
+    }
+
+    #[test]
+    fn cone_is_around_the_aim_rather_than_the_heading() {
+        let mut app = homing_app();
+        // Fired with 25 degrees of spread, the target is 45 degrees off the heading
+        let bullet = spawn_bullet(&mut app, Vec2::from_angle(25_f32.to_radians()), Vec2::X);
+        let target = spawn_target(&mut app, 1, -20., 400.);
+
+        app.update();
+        assert_eq!(target_of(&app, bullet), Some(target));
+    }
+
+    #[test]
+    fn turns_no_faster_than_the_turn_rate() {
+        let mut app = homing_app();
+        let bullet = spawn_bullet(&mut app, Vec2::X, Vec2::X);
+        spawn_target(&mut app, 1, 25., 500.);
+
+        app.update();
+        let velocity = app.world.get::<Velocity>(bullet).unwrap().linvel;
+        let turned = Vec2::X.angle_between(velocity).to_degrees();
+        assert!((turned - HOMING.turn_rate * DT).abs() < 1e-3, "turned {turned} degrees");
+        assert!((velocity.length() - 100.).abs() < 1e-3);
+    }
+}
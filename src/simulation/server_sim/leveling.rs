@@ -0,0 +1,284 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+use crate::ServerSet::ServerUpdate;
+use crate::simulation::{Lobby, Object};
+use crate::simulation::events::OnPlayerDataChangedEvent;
+use crate::simulation::server_sim::game_mode::capture_the_flag::{FlagAction, FlagEvent};
+use crate::simulation::server_sim::player::{Health, OnAssistEvent, OnHealthChangedEvent, OnKillEvent, Player, PlayerTurret};
+use crate::simulation::server_sim::player::PlayerSystemStage::ApplyDamage;
+use crate::utils::networking::messages::PlayerId;
+
+pub const MAX_LEVEL: u32 = 20;
+/// How many times a single stat can be upgraded.
+pub const MAX_RANK: u32 = 5;
+const KILL_XP: u32 = 100;
+const ASSIST_XP: u32 = 50;
+const CAPTURE_XP: u32 = 150;
+/// For every second spent on a control point held by your team.
+pub const HILL_XP: u32 = 5;
+
+/// Players earn experience for kills and objectives, level up during the match and spend the
+/// points they get on stat upgrades. Levels are kept in `PlayerData`, so they're replicated with
+/// the rest of it and last until the match ends.
+pub struct LevelingPlugin;
+
+impl Plugin for LevelingPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_event::<ExperienceEvent>()
+            .add_event::<OnUpgradeChoiceEvent>()
+            .add_systems(
+                (
+                    award_kill_experience.after(ApplyDamage),
+                    award_capture_experience,
+                    gain_experience.after(award_kill_experience).after(award_capture_experience),
+                    choose_upgrade,
+                    apply_stat_upgrades.after(choose_upgrade).before(ApplyDamage),
+                ).in_set(ServerUpdate)
+            );
+    }
+}
+
+/// Gives a player experience, every source of it goes through this event.
+pub struct ExperienceEvent {
+    pub player_id: PlayerId,
+    pub amount: u32,
+}
+
+/// Sent by a client spending a point on a stat.
+pub struct OnUpgradeChoiceEvent {
+    pub player_id: PlayerId,
+    pub stat: Stat,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Stat {
+    MaxHealth,
+    Speed,
+    Accel,
+    BulletSpeed,
+    Damage,
+    Reload,
+}
+
+impl Stat {
+    pub const ALL: [Stat; 6] = [Stat::MaxHealth, Stat::Speed, Stat::Accel, Stat::BulletSpeed, Stat::Damage, Stat::Reload];
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Stat::MaxHealth => "Max health",
+            Stat::Speed => "Speed",
+            Stat::Accel => "Acceleration",
+            Stat::BulletSpeed => "Bullet speed",
+            Stat::Damage => "Damage",
+            Stat::Reload => "Reload",
+        }
+    }
+
+    /// Extra health for every rank of max health, and the fraction every rank adds for the
+    /// other stats.
+    fn per_rank(&self) -> f32 {
+        match self {
+            Stat::MaxHealth => 20.,
+            Stat::Speed => 0.08,
+            Stat::Accel => 0.1,
+            Stat::BulletSpeed => 0.1,
+            Stat::Damage => 0.1,
+            Stat::Reload => 0.1,
+        }
+    }
+}
+
+/// How many times each stat has been upgraded.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StatUpgrades {
+    pub max_health: u32,
+    pub speed: u32,
+    pub accel: u32,
+    pub bullet_speed: u32,
+    pub damage: u32,
+    pub reload: u32,
+}
+
+impl StatUpgrades {
+    pub fn rank(&self, stat: Stat) -> u32 {
+        match stat {
+            Stat::MaxHealth => self.max_health,
+            Stat::Speed => self.speed,
+            Stat::Accel => self.accel,
+            Stat::BulletSpeed => self.bullet_speed,
+            Stat::Damage => self.damage,
+            Stat::Reload => self.reload,
+        }
+    }
+
+    fn rank_mut(&mut self, stat: Stat) -> &mut u32 {
+        match stat {
+            Stat::MaxHealth => &mut self.max_health,
+            Stat::Speed => &mut self.speed,
+            Stat::Accel => &mut self.accel,
+            Stat::BulletSpeed => &mut self.bullet_speed,
+            Stat::Damage => &mut self.damage,
+            Stat::Reload => &mut self.reload,
+        }
+    }
+
+    /// What the base value of a stat is multiplied by. Reload makes shots, reloads and cooling
+    /// down this much faster.
+    pub fn multiplier(&self, stat: Stat) -> f32 {
+        1. + stat.per_rank() * self.rank(stat) as f32
+    }
+
+    pub fn bonus_health(&self) -> f32 {
+        Stat::MaxHealth.per_rank() * self.max_health as f32
+    }
+}
+
+/// A player's level in the current match.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Progression {
+    pub level: u32,
+    /// Experience towards the next level.
+    pub xp: u32,
+    /// Upgrades that can still be bought, one is earned every level.
+    pub points: u32,
+    pub upgrades: StatUpgrades,
+}
+
+impl Default for Progression {
+    fn default() -> Self {
+        Progression {
+            level: 1,
+            xp: 0,
+            points: 0,
+            upgrades: StatUpgrades::default(),
+        }
+    }
+}
+
+impl Progression {
+    /// Experience needed to get from `level` to the next one.
+    pub fn xp_to_next_level(level: u32) -> u32 {
+        100 * level
+    }
+
+    /// Returns how many levels were gained.
+    pub fn add_xp(&mut self, amount: u32) -> u32 {
+        let before = self.level;
+        self.xp += amount;
+        while self.level < MAX_LEVEL && self.xp >= Self::xp_to_next_level(self.level) {
+            self.xp -= Self::xp_to_next_level(self.level);
+            self.level += 1;
+            self.points += 1;
+        }
+        if self.level == MAX_LEVEL {
+            self.xp = 0;
+        }
+        self.level - before
+    }
+
+    /// Spends a point on the stat. Returns false if there are no points left or it's maxed out.
+    pub fn upgrade(&mut self, stat: Stat) -> bool {
+        let rank = self.upgrades.rank_mut(stat);
+        if self.points == 0 || *rank >= MAX_RANK { return false; }
+        *rank += 1;
+        self.points -= 1;
+        true
+    }
+}
+
+/// The upgrades that have been applied to a tank's components, so they can be brought up to
+/// date with the player's `Progression`.
+#[derive(Component, Debug, Default)]
+pub struct AppliedUpgrades(pub StatUpgrades);
+
+fn award_kill_experience(
+    mut kill_events: EventReader<OnKillEvent>,
+    mut assist_events: EventReader<OnAssistEvent>,
+    mut xp_writer: EventWriter<ExperienceEvent>,
+) {
+    kill_events.iter().for_each(|e| {
+        xp_writer.send(ExperienceEvent { player_id: e.attacker_id, amount: KILL_XP });
+    });
+    assist_events.iter().for_each(|e| {
+        xp_writer.send(ExperienceEvent { player_id: e.assister_id, amount: ASSIST_XP });
+    });
+}
+
+fn award_capture_experience(
+    mut flag_events: EventReader<FlagEvent>,
+    mut xp_writer: EventWriter<ExperienceEvent>,
+) {
+    flag_events.iter()
+        .filter(|e| e.action == FlagAction::Captured)
+        .filter_map(|e| e.player_id)
+        .for_each(|player_id| {
+            xp_writer.send(ExperienceEvent { player_id, amount: CAPTURE_XP });
+        });
+}
+
+fn gain_experience(
+    mut xp_events: EventReader<ExperienceEvent>,
+    mut lobby: ResMut<Lobby>,
+    mut changed_writer: EventWriter<OnPlayerDataChangedEvent>,
+) {
+    xp_events.iter().for_each(|e| {
+        let Some(data) = lobby.player_data.get_mut(&e.player_id) else { return; };
+        if data.progression.add_xp(e.amount) > 0 {
+            info!("Player {} reached level {}", e.player_id, data.progression.level);
+        }
+        changed_writer.send(OnPlayerDataChangedEvent { player_id: e.player_id });
+    });
+}
+
+fn choose_upgrade(
+    mut choice_events: EventReader<OnUpgradeChoiceEvent>,
+    mut lobby: ResMut<Lobby>,
+    mut changed_writer: EventWriter<OnPlayerDataChangedEvent>,
+) {
+    choice_events.iter().for_each(|e| {
+        let Some(data) = lobby.player_data.get_mut(&e.player_id) else { return; };
+        if data.progression.upgrade(e.stat) {
+            changed_writer.send(OnPlayerDataChangedEvent { player_id: e.player_id });
+        }
+    });
+}
+
+/// Brings every tank's stats up to date with its player's upgrades, both when they're bought and
+/// when the tank respawns. Stats are scaled rather than set, so running boosts and buffs are kept.
+fn apply_stat_upgrades(
+    mut players: Query<(&mut Player, &Object, &mut Health, &mut AppliedUpgrades, &Children)>,
+    mut turrets: Query<&mut PlayerTurret>,
+    lobby: Res<Lobby>,
+    mut health_writer: EventWriter<OnHealthChangedEvent>,
+) {
+    players.iter_mut().for_each(|(mut player, object, mut health, mut applied, children)| {
+        let Some(data) = lobby.player_data.get(&player.id) else { return; };
+        let upgrades = data.progression.upgrades;
+        let old = applied.0;
+        if upgrades == old { return; }
+
+        player.max_speed *= upgrades.multiplier(Stat::Speed) / old.multiplier(Stat::Speed);
+        player.accel *= upgrades.multiplier(Stat::Accel) / old.multiplier(Stat::Accel);
+
+        let bonus_health = upgrades.bonus_health() - old.bonus_health();
+        if bonus_health != 0. {
+            health.max_health += bonus_health;
+            health.health = (health.health + bonus_health.max(0.)).min(health.max_health);
+            health_writer.send(OnHealthChangedEvent {
+                object_id: object.id,
+                health: health.health,
+                max_health: health.max_health,
+            });
+        }
+
+        children.iter().for_each(|&child| {
+            let Ok(mut turret) = turrets.get_mut(child) else { return; };
+            turret.damage_multiplier *= upgrades.multiplier(Stat::Damage) / old.multiplier(Stat::Damage);
+            turret.bullet_speed_multiplier *= upgrades.multiplier(Stat::BulletSpeed) / old.multiplier(Stat::BulletSpeed);
+            turret.reload_multiplier *= upgrades.multiplier(Stat::Reload) / old.multiplier(Stat::Reload);
+        });
+
+        applied.0 = upgrades;
+    });
+}
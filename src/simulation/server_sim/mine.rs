@@ -0,0 +1,161 @@
+use bevy::prelude::*;
+use crate::display::sprite_updater::{AutoSorted, BULLET_LAYER};
+use crate::asset_loader::components::SpriteEnum;
+use crate::ServerSet::ServerUpdate;
+use crate::simulation::{Lobby, Object};
+use crate::simulation::server_sim::ai::AiTank;
+use crate::simulation::server_sim::bullet::BulletSystemStage::CollisionHandle;
+use crate::simulation::server_sim::bullet::is_friendly_fire;
+use crate::simulation::server_sim::explosion::ExplodeEvent;
+use crate::simulation::server_sim::player::Player;
+use crate::simulation::server_sim::weapon::ExplosionDefinition;
+use crate::utils::commands::despawn::CustomDespawnExt;
+use crate::utils::networking::messages::PlayerId;
+use crate::utils::prefabs::TANK_COLLIDER_RADIUS;
+
+/// How many mines a player can have down at once.
+pub const MAX_MINES: usize = 3;
+/// Seconds between two mines from the same tank.
+const DROP_COOLDOWN: f32 = 0.5;
+const ARM_DELAY: f32 = 1.5;
+const MINE_LIFETIME: f32 = 60.;
+const MINE_RADIUS: f32 = 20.;
+const MINE_EXPLOSION: ExplosionDefinition = ExplosionDefinition {
+    radius: 180.,
+    damage: 60.,
+    knockback: 120.,
+    damages_owner: false,
+};
+
+/// Players drop proximity mines behind them, which only they and their teammates are sent.
+pub struct MinePlugin;
+
+impl Plugin for MinePlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_event::<OnDropMineEvent>()
+            .add_systems(
+                (
+                    drop_mines,
+                    tick_mines.after(drop_mines),
+                    trigger_mines.after(tick_mines),
+                ).in_set(ServerUpdate).before(CollisionHandle)
+            );
+    }
+}
+
+/// Sent by a client pressing the mine key.
+pub struct OnDropMineEvent {
+    pub player_id: PlayerId,
+}
+
+/// Seconds until the tank can drop its next mine.
+#[derive(Component)]
+pub struct MineCooldown(pub f32);
+
+#[derive(Component)]
+pub struct Mine {
+    pub owner: Entity,
+    pub owner_id: PlayerId,
+    /// Seconds until the mine can go off.
+    pub arm_time: f32,
+    pub lifetime: f32,
+}
+
+impl Mine {
+    pub fn is_armed(&self) -> bool {
+        self.arm_time <= 0.
+    }
+
+    /// Whether the mine should be sent to this player.
+    pub fn is_visible_to(&self, player_id: PlayerId, lobby: &Lobby) -> bool {
+        Mine::is_visible_from(self.owner_id, player_id, lobby)
+    }
+
+    /// Same as `is_visible_to`, for when only the owner is left, like once the mine is gone.
+    pub fn is_visible_from(owner_id: PlayerId, player_id: PlayerId, lobby: &Lobby) -> bool {
+        owner_id == player_id || lobby.are_teammates(owner_id, player_id)
+    }
+}
+
+/// Drops a mine under the tank of every player who asked for one, unless they dropped one
+/// too recently or already have `MAX_MINES` down.
+fn drop_mines(
+    mut drop_events: EventReader<OnDropMineEvent>,
+    mut players: Query<(Entity, &Player, &Transform, Option<&mut MineCooldown>)>,
+    mines: Query<&Mine>,
+    mut commands: Commands,
+    time: Res<Time>,
+) {
+    players.iter_mut().for_each(|(_, _, _, cooldown)| {
+        if let Some(mut cooldown) = cooldown {
+            cooldown.0 -= time.delta_seconds();
+        }
+    });
+
+    drop_events.iter().for_each(|e| {
+        let Some((ent, player, trans, cooldown)) = players.iter()
+            .find(|(_, player, _, _)| player.id == e.player_id) else { return; };
+        if cooldown.map_or(false, |cooldown| cooldown.0 > 0.) { return; }
+        if mines.iter().filter(|mine| mine.owner == ent).count() >= MAX_MINES { return; }
+
+        commands.entity(ent).insert(MineCooldown(DROP_COOLDOWN));
+        commands.spawn((
+            Name::from("Mine"),
+            Mine {
+                owner: ent,
+                owner_id: player.id,
+                arm_time: ARM_DELAY,
+                lifetime: MINE_LIFETIME,
+            },
+            AutoSorted,
+            SpriteEnum::Mine,
+            TransformBundle::from_transform(Transform::from_translation(
+                trans.translation.truncate().extend(BULLET_LAYER))),
+            Object::new(),
+        ));
+    });
+}
+
+/// Arms new mines, and removes mines that timed out or whose owner's tank is gone.
+fn tick_mines(
+    mut mines: Query<(Entity, &mut Mine)>,
+    players: Query<(), With<Player>>,
+    mut commands: Commands,
+    time: Res<Time>,
+) {
+    mines.iter_mut().for_each(|(ent, mut mine)| {
+        mine.arm_time -= time.delta_seconds();
+        mine.lifetime -= time.delta_seconds();
+        if players.get(mine.owner).is_err() {
+            mine.lifetime = 0.;
+        }
+        if mine.lifetime <= 0. {
+            commands.entity(ent).custom_despawn();
+        }
+    });
+}
+
+fn trigger_mines(
+    mines: Query<(Entity, &Mine, &Transform)>,
+    targets: Query<(Entity, &Transform), With<Player>>,
+    players: Query<(&Player, Option<&AiTank>)>,
+    lobby: Res<Lobby>,
+    mut explode_writer: EventWriter<ExplodeEvent>,
+    mut commands: Commands,
+) {
+    mines.iter()
+        .filter(|(_, mine, _)| mine.is_armed() && mine.lifetime > 0.)
+        .for_each(|(ent, mine, trans)| {
+            let position = trans.translation.truncate();
+            let triggered = targets.iter().any(|(target, target_trans)| {
+                target != mine.owner &&
+                    !is_friendly_fire(Some(mine.owner), target, &players, &lobby) &&
+                    target_trans.translation.truncate().distance(position) <= TANK_COLLIDER_RADIUS + MINE_RADIUS
+            });
+            if !triggered { return; }
+
+            explode_writer.send(ExplodeEvent { position, owner: Some(mine.owner), explosion: MINE_EXPLOSION });
+            commands.entity(ent).custom_despawn();
+        });
+}
@@ -1,13 +1,23 @@
 use bevy::app::App;
 use bevy::prelude::{Commands, IntoSystemSetConfig, OnUpdate, Plugin, States, Window, World};
 use crate::ServerSet::ServerUpdate;
+use crate::simulation::server_sim::ai::AiPlugin;
+use crate::simulation::server_sim::boundary::BoundaryPlugin;
 use crate::simulation::server_sim::bullet::BulletPlugin;
+use crate::simulation::server_sim::config::ServerConfig;
+use crate::simulation::server_sim::explosion::ExplosionPlugin;
+use crate::simulation::server_sim::hitscan::HitscanPlugin;
+use crate::simulation::server_sim::mine::MinePlugin;
 use crate::simulation::server_sim::init::InitPlugin;
-use crate::simulation::server_sim::match_ffa::MatchFFAPlugin;
+use crate::simulation::server_sim::leveling::LevelingPlugin;
+use crate::simulation::server_sim::game_mode::GameModePlugin;
 use crate::simulation::server_sim::physics::PhysicsPlugin;
+use crate::simulation::server_sim::pickup::PickupPlugin;
 use crate::simulation::server_sim::player::PlayerPlugin;
 use crate::simulation::server_sim::respawn::RespawnPlugin;
 use crate::simulation::server_sim::spawn::SpawnPlugin;
+use crate::simulation::server_sim::skill::SkillPlugin;
+use crate::simulation::server_sim::weapon::WeaponPlugin;
 use crate::utils::commands::despawn::CustomDespawnExt;
 
 pub mod player;
@@ -15,8 +25,19 @@ pub mod bullet;
 pub mod physics;
 pub mod respawn;
 pub mod spawn;
-pub mod match_ffa;
+pub mod game_mode;
 pub mod init;
+pub mod config;
+pub mod boundary;
+pub mod ai;
+pub mod weapon;
+pub mod explosion;
+pub mod hitscan;
+pub mod homing;
+pub mod mine;
+pub mod skill;
+pub mod leveling;
+pub mod pickup;
 
 pub struct ServerSimulationPlugin;
 
@@ -24,16 +45,26 @@ impl Plugin for ServerSimulationPlugin {
     fn build(&self, app: &mut App) {
         app
             .add_state::<InGameState>()
+            .insert_resource(ServerConfig::from_args())
             .configure_set(ServerUpdate.in_set(OnUpdate(InGameState::Playing)));
 
         app
             .add_plugin(InitPlugin)
-            .add_plugin(MatchFFAPlugin)
+            .add_plugin(GameModePlugin)
             .add_plugin(PlayerPlugin)
             .add_plugin(BulletPlugin)
             .add_plugin(PhysicsPlugin)
             .add_plugin(RespawnPlugin)
-            .add_plugin(SpawnPlugin);
+            .add_plugin(SpawnPlugin)
+            .add_plugin(BoundaryPlugin)
+            .add_plugin(AiPlugin)
+            .add_plugin(WeaponPlugin)
+            .add_plugin(ExplosionPlugin)
+            .add_plugin(HitscanPlugin)
+            .add_plugin(MinePlugin)
+            .add_plugin(SkillPlugin)
+            .add_plugin(LevelingPlugin)
+            .add_plugin(PickupPlugin);
     }
 }
 
@@ -0,0 +1,316 @@
+use bevy::prelude::*;
+use bevy_rapier2d::prelude::{ActiveEvents, Collider, RapierContext, Sensor};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use crate::asset_loader::components::SpriteEnum;
+use crate::asset_loader::resources::SkillAssets;
+use crate::display::sprite_updater::{AutoSorted, BULLET_LAYER};
+use crate::ServerSet::ServerUpdate;
+use crate::simulation::Object;
+use crate::simulation::server_sim::player::{Health, OnHealEvent, OnPlayerDeathEvent, Player, PlayerTurret};
+use crate::simulation::server_sim::player::PlayerSystemStage::ApplyDamage;
+use crate::simulation::server_sim::skill::{DEFAULT_SKILLS, SkillDefinitions, SkillLoadout};
+use crate::utils::commands::despawn::CustomDespawnExt;
+
+pub const PICKUP_RADIUS: f32 = 24.;
+/// Seconds before a pickup dropped by a tank disappears, spawned ones stay until collected.
+const DROP_LIFETIME: f32 = 30.;
+/// Chance a tank without any skills of its own to drop leaves something else behind.
+const DROP_CHANCE: f32 = 0.5;
+/// Skills dropped by the same tank are spread out this far so they don't overlap.
+const DROP_SPACING: f32 = 50.;
+
+/// Health packs, temporary buffs and skill items lying around the map, collected by driving
+/// over them. They come from the spawners in the map data and from defeated tanks, which drop
+/// the skills they picked up.
+pub struct PickupPlugin;
+
+impl Plugin for PickupPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            (
+                run_pickup_spawners,
+                drop_loot.after(ApplyDamage),
+                expire_pickups,
+                collect_pickups.after(expire_pickups),
+                tick_buffs.after(collect_pickups),
+            ).in_set(ServerUpdate)
+        );
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum PickupKind {
+    /// Only collected by tanks that are hurt.
+    Health { amount: f32 },
+    Buff { effect: BuffEffect, duration: f32 },
+    /// Equips the skill with this name, only collected by tanks with a free slot that don't
+    /// have it yet.
+    Skill { name: String },
+}
+
+impl PickupKind {
+    /// The pickups the map editor can place spawners for.
+    pub const SPAWNABLE: [PickupKind; 3] = [
+        PickupKind::Health { amount: 50. },
+        PickupKind::Buff { effect: BuffEffect::Speed { multiplier: 1.3 }, duration: 10. },
+        PickupKind::Buff { effect: BuffEffect::Damage { multiplier: 1.3 }, duration: 10. },
+    ];
+
+    pub fn name(&self) -> &str {
+        match self {
+            PickupKind::Health { .. } => "Health pack",
+            PickupKind::Buff { effect: BuffEffect::Speed { .. }, .. } => "Speed",
+            PickupKind::Buff { effect: BuffEffect::Damage { .. }, .. } => "Damage",
+            PickupKind::Skill { name } => name,
+        }
+    }
+
+    pub fn sprite(&self) -> SpriteEnum {
+        match self {
+            PickupKind::Health { .. } => SpriteEnum::HealthPack,
+            PickupKind::Buff { effect: BuffEffect::Speed { .. }, .. } => SpriteEnum::SpeedPickup,
+            PickupKind::Buff { effect: BuffEffect::Damage { .. }, .. } => SpriteEnum::DamagePickup,
+            PickupKind::Skill { .. } => SpriteEnum::SkillPickup,
+        }
+    }
+
+    /// Something other than a skill, left behind by a tank that had none to drop.
+    fn random_drop(rng: &mut impl Rng) -> PickupKind {
+        match rng.gen_range(0..3) {
+            0 => PickupKind::Buff { effect: BuffEffect::Speed { multiplier: 1.3 }, duration: 10. },
+            1 => PickupKind::Buff { effect: BuffEffect::Damage { multiplier: 1.3 }, duration: 10. },
+            _ => PickupKind::Health { amount: 40. },
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum BuffEffect {
+    /// Multiplies the tank's top speed and acceleration.
+    Speed { multiplier: f32 },
+    Damage { multiplier: f32 },
+}
+
+/// Keeps a pickup at a spot on the map, placed in `MapData::pickup_spawners`.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PickupSpawner {
+    pub position: Vec2,
+    pub kind: PickupKind,
+    /// Seconds after its pickup is collected until the next one appears.
+    pub respawn_time: f32,
+}
+
+/// A `PickupSpawner` in the world, only run on the server.
+#[derive(Component)]
+pub struct ActiveSpawner {
+    pub spawner: PickupSpawner,
+    pub pickup: Option<Entity>,
+    /// Seconds until the next pickup appears.
+    pub timer: f32,
+}
+
+impl ActiveSpawner {
+    pub fn new(spawner: PickupSpawner) -> Self {
+        ActiveSpawner {
+            spawner,
+            pickup: None,
+            timer: 0.,
+        }
+    }
+}
+
+#[derive(Component)]
+pub struct Pickup {
+    pub kind: PickupKind,
+    /// Seconds left for dropped pickups.
+    pub lifetime: Option<f32>,
+    /// Set once it's been collected or timed out, so it isn't collected twice before it's despawned.
+    pub taken: bool,
+}
+
+/// Buffs a tank picked up, each undone once it runs out.
+#[derive(Component, Debug, Default)]
+pub struct Buffs {
+    pub buffs: Vec<ActiveBuff>,
+}
+
+#[derive(Debug)]
+pub struct ActiveBuff {
+    pub effect: BuffEffect,
+    pub remaining: f32,
+    /// Whether the effect has been applied to the tank yet.
+    pub applied: bool,
+}
+
+fn spawn_pickup(commands: &mut Commands, kind: PickupKind, position: Vec2, lifetime: Option<f32>) -> Entity {
+    commands.spawn((
+        Name::from("Pickup"),
+        AutoSorted,
+        kind.sprite(),
+        Pickup { kind, lifetime, taken: false },
+        TransformBundle::from_transform(Transform::from_translation(position.extend(BULLET_LAYER))),
+        Object::new(),
+        Collider::ball(PICKUP_RADIUS),
+        Sensor,
+        ActiveEvents::COLLISION_EVENTS,
+    )).id()
+}
+
+/// Puts a new pickup down once the last one from a spawner has been collected and its respawn
+/// time is up.
+fn run_pickup_spawners(
+    mut spawners: Query<&mut ActiveSpawner>,
+    pickups: Query<&Pickup>,
+    mut commands: Commands,
+    time: Res<Time>,
+) {
+    spawners.iter_mut().for_each(|mut spawner| {
+        if let Some(pickup) = spawner.pickup {
+            if pickups.get(pickup).map_or(false, |pickup| !pickup.taken) { return; }
+            spawner.pickup = None;
+            spawner.timer = spawner.spawner.respawn_time;
+        }
+
+        spawner.timer -= time.delta_seconds();
+        if spawner.timer <= 0. {
+            let PickupSpawner { position, kind, .. } = spawner.spawner.clone();
+            spawner.pickup = Some(spawn_pickup(&mut commands, kind, position, None));
+        }
+    });
+}
+
+/// Defeated tanks drop every skill they picked up, or sometimes a health pack or a buff if they
+/// only had the default ones.
+fn drop_loot(
+    mut death_events: EventReader<OnPlayerDeathEvent>,
+    loadouts: Query<&SkillLoadout>,
+    mut commands: Commands,
+) {
+    let mut rng = rand::thread_rng();
+    death_events.iter().for_each(|e| {
+        let skills = loadouts.get(e.entity).map(|loadout| {
+            loadout.slots.iter().flatten()
+                .filter(|slot| !DEFAULT_SKILLS.contains(&slot.name.as_str()))
+                .map(|slot| slot.name.clone())
+                .collect::<Vec<_>>()
+        }).unwrap_or_default();
+
+        if skills.is_empty() {
+            if rng.gen::<f32>() < DROP_CHANCE {
+                spawn_pickup(&mut commands, PickupKind::random_drop(&mut rng), e.position, Some(DROP_LIFETIME));
+            }
+            return;
+        }
+
+        let count = skills.len();
+        skills.into_iter().enumerate().for_each(|(i, name)| {
+            let offset = if count > 1 {
+                Vec2::from_angle(std::f32::consts::TAU * i as f32 / count as f32) * DROP_SPACING
+            } else {
+                Vec2::ZERO
+            };
+            spawn_pickup(&mut commands, PickupKind::Skill { name }, e.position + offset, Some(DROP_LIFETIME));
+        });
+    });
+}
+
+fn expire_pickups(
+    mut pickups: Query<(Entity, &mut Pickup)>,
+    mut commands: Commands,
+    time: Res<Time>,
+) {
+    pickups.iter_mut().for_each(|(ent, mut pickup)| {
+        let Some(lifetime) = pickup.lifetime else { return; };
+        let lifetime = lifetime - time.delta_seconds();
+        pickup.lifetime = Some(lifetime);
+        if lifetime <= 0. && !pickup.taken {
+            pickup.taken = true;
+            commands.entity(ent).custom_despawn();
+        }
+    });
+}
+
+/// Gives a pickup to the first tank touching it that can use it.
+fn collect_pickups(
+    mut pickups: Query<(Entity, &mut Pickup)>,
+    mut tanks: Query<(Entity, &Health, &mut Buffs, &mut SkillLoadout)>,
+    rapier_context: Res<RapierContext>,
+    (skill_assets, definitions): (Res<SkillAssets>, Res<Assets<SkillDefinitions>>),
+    mut heal_writer: EventWriter<OnHealEvent>,
+    mut commands: Commands,
+) {
+    pickups.iter_mut().filter(|(_, pickup)| !pickup.taken).for_each(|(pickup_ent, mut pickup)| {
+        let collected = tanks.iter_mut()
+            .filter(|(tank, ..)| rapier_context.intersection_pair(pickup_ent, *tank) == Some(true))
+            .any(|(tank, health, mut buffs, mut loadout)| match &pickup.kind {
+                PickupKind::Health { amount } => {
+                    if health.health >= health.max_health { return false; }
+                    heal_writer.send(OnHealEvent { target: tank, amount: *amount });
+                    true
+                }
+                PickupKind::Buff { effect, duration } => {
+                    buffs.buffs.push(ActiveBuff { effect: *effect, remaining: *duration, applied: false });
+                    true
+                }
+                PickupKind::Skill { name } => {
+                    let Some(definition) = skill_assets.get(&definitions, name) else {
+                        warn!("Pickup has unknown skill '{name}'");
+                        return false;
+                    };
+                    loadout.equip(name, definition)
+                }
+            });
+
+        if collected {
+            pickup.taken = true;
+            commands.entity(pickup_ent).custom_despawn();
+        }
+    });
+}
+
+/// Applies new buffs and undoes the ones that ran out. Like boosting, buffs scale the tank's
+/// stats rather than setting them, so they stack with each other and with upgrades.
+fn tick_buffs(
+    mut tanks: Query<(&mut Buffs, &mut Player, &Children)>,
+    mut turrets: Query<&mut PlayerTurret>,
+    time: Res<Time>,
+) {
+    tanks.iter_mut().for_each(|(mut buffs, mut player, children)| {
+        buffs.buffs.iter_mut().for_each(|buff| {
+            if !buff.applied {
+                buff.applied = true;
+                scale_by_buff(buff.effect, 1., &mut player, children, &mut turrets);
+            }
+            buff.remaining -= time.delta_seconds();
+            if buff.remaining <= 0. {
+                scale_by_buff(buff.effect, -1., &mut player, children, &mut turrets);
+            }
+        });
+        buffs.buffs.retain(|buff| buff.remaining > 0.);
+    });
+}
+
+/// Scales the tank's stats by the buff's multiplier raised to `power`, 1 to apply it and -1 to
+/// undo it.
+fn scale_by_buff(
+    effect: BuffEffect,
+    power: f32,
+    player: &mut Player,
+    children: &Children,
+    turrets: &mut Query<&mut PlayerTurret>,
+) {
+    match effect {
+        BuffEffect::Speed { multiplier } => {
+            player.max_speed *= multiplier.powf(power);
+            player.accel *= multiplier.powf(power);
+        }
+        BuffEffect::Damage { multiplier } => {
+            children.iter().for_each(|&child| {
+                if let Ok(mut turret) = turrets.get_mut(child) {
+                    turret.damage_multiplier *= multiplier.powf(power);
+                }
+            });
+        }
+    }
+}
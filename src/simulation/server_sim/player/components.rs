@@ -1,7 +1,10 @@
 use bevy::prelude::{Component, Entity, Reflect, Resource};
 use bevy::math::Vec2;
+use bevy::utils::HashMap;
 use serde::{Deserialize, Serialize};
 use crate::client_networking::ClientInput;
+use crate::simulation::server_sim::skill::SKILL_SLOTS;
+use crate::simulation::server_sim::weapon::{DEFAULT_WEAPON, WeaponState};
 use crate::utils::networking::messages::PlayerId;
 
 #[derive(Component)]
@@ -13,17 +16,35 @@ const DEFAULT_HEALTH: f32 = 100.0;
 pub struct Health {
     pub max_health: f32,
     pub health: f32,
+    /// Seconds since the tank last took damage, regeneration only starts once it's been long enough.
+    pub since_damage: f32,
+    /// Seconds until regeneration heals again.
+    pub regen_timer: f32,
 }
 
-impl Default for Health {
-    fn default() -> Self {
+impl Health {
+    pub fn new(health: f32) -> Self {
         Self {
-            max_health: DEFAULT_HEALTH,
-            health: DEFAULT_HEALTH,
+            max_health: health,
+            health,
+            since_damage: 0.,
+            regen_timer: 0.,
         }
     }
 }
 
+impl Default for Health {
+    fn default() -> Self {
+        Self::new(DEFAULT_HEALTH)
+    }
+}
+
+/// Damage taken from each other player during this life of the tank, for assists.
+#[derive(Component, Debug, Default)]
+pub struct DamageTaken {
+    pub by_player: HashMap<PlayerId, f32>,
+}
+
 #[derive(Component, Clone)]
 pub struct Player {
     pub id: PlayerId,
@@ -47,7 +68,16 @@ impl Player {
 pub struct PlayerTurret {
     pub owner: Option<Entity>,
     pub direction: Vec2,
-    pub bullet_speed: f32,
+    /// Name of the equipped weapon in the `WeaponDefinitions`.
+    pub weapon: String,
+    /// Seconds until the weapon can fire again.
+    pub cooldown: f32,
+    pub state: WeaponState,
+    /// Set from the owner's stat upgrades.
+    pub damage_multiplier: f32,
+    pub bullet_speed_multiplier: f32,
+    /// Shots, reloads and cooling down are this much faster.
+    pub reload_multiplier: f32,
 }
 
 impl Default for PlayerTurret {
@@ -55,7 +85,12 @@ impl Default for PlayerTurret {
         PlayerTurret {
             owner: None,
             direction: Vec2::default(),
-            bullet_speed: 600.,
+            weapon: DEFAULT_WEAPON.to_owned(),
+            cooldown: 0.,
+            state: WeaponState::default(),
+            damage_multiplier: 1.,
+            bullet_speed_multiplier: 1.,
+            reload_multiplier: 1.,
         }
     }
 }
@@ -66,6 +101,7 @@ pub struct PlayerInput {
     pub movement: Vec2,
     pub mouse_pos: Vec2,
     pub fire_bullet: bool,
+    pub skills: [bool; SKILL_SLOTS],
 }
 
 impl From<ClientInput> for PlayerInput {
@@ -74,6 +110,7 @@ impl From<ClientInput> for PlayerInput {
             movement: client_input.movement,
             mouse_pos: client_input.mouse_pos,
             fire_bullet: client_input.fire_bullet,
+            skills: client_input.skills,
         }
     }
 }
@@ -7,6 +7,7 @@ pub use utils::*;
 use bevy::app::App;
 pub use components::*;
 use bevy::prelude::*;
+use bevy::utils::HashMap;
 use crate::ServerSet::ServerUpdate;
 use crate::utils::networking::messages::PlayerId;
 use crate::simulation::ObjectId;
@@ -18,18 +19,52 @@ impl Plugin for PlayerPlugin {
         app
             .add_event::<OnPlayerDeathEvent>()
             .add_event::<OnKillEvent>()
+            .add_event::<OnAssistEvent>()
+            .add_event::<OnDamageDealtEvent>()
             .add_event::<OnHealthChangedEvent>()
+            .add_event::<OnDamageEvent>()
+            .add_event::<OnHealEvent>()
             .add_systems(
                 (
                     systems::player_move,
-                    systems::player_turret_rotate
+                    systems::player_turret_rotate,
+                    systems::apply_damage.in_set(PlayerSystemStage::ApplyDamage),
+                    systems::award_assists.after(PlayerSystemStage::ApplyDamage),
+                    systems::regenerate_health.after(PlayerSystemStage::ApplyDamage),
+                    systems::apply_healing.after(systems::regenerate_health),
                 ).in_set(ServerUpdate)
             );
     }
 }
 
+#[derive(SystemSet, Debug, Eq, PartialEq, Hash, Clone)]
+pub enum PlayerSystemStage {
+    ApplyDamage,
+}
+
+/// Every source of damage goes through this event, so health, deaths and kills are handled in one place.
+pub struct OnDamageEvent {
+    pub victim: Entity,
+    pub attacker: Option<Entity>,
+    pub damage: f32,
+}
+
+/// Every source of healing goes through this event, like damage does through `OnDamageEvent`.
+pub struct OnHealEvent {
+    pub target: Entity,
+    pub amount: f32,
+}
+
 pub struct OnPlayerDeathEvent {
     pub player_id: PlayerId,
+    /// The tank, which is despawned once this frame's commands are applied.
+    pub entity: Entity,
+    pub position: Vec2,
+    /// The player whose hit finished the tank off, unless it was its own or nobody's.
+    pub killer_id: Option<PlayerId>,
+    /// Damage dealt by every player during this life of the tank, the killer included.
+    pub damage_taken: HashMap<PlayerId, f32>,
+    pub max_health: f32,
 }
 
 pub struct OnKillEvent {
@@ -37,6 +72,19 @@ pub struct OnKillEvent {
     pub victim_id: PlayerId,
 }
 
+/// Sent alongside `OnKillEvent` for everyone else who did enough damage to the victim.
+pub struct OnAssistEvent {
+    pub assister_id: PlayerId,
+    pub victim_id: PlayerId,
+}
+
+/// Damage a player's tank actually took from another player, not counting overkill.
+pub struct OnDamageDealtEvent {
+    pub attacker_id: PlayerId,
+    pub victim_id: PlayerId,
+    pub damage: f32,
+}
+
 pub struct OnHealthChangedEvent {
     pub object_id: ObjectId,
     pub health: f32,
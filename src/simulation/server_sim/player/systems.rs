@@ -1,9 +1,18 @@
-use bevy::prelude::{Children, GlobalTransform, Query, Res, Time, Transform, With};
+use bevy::prelude::{Children, Commands, Entity, EventReader, EventWriter, GlobalTransform, Query, Res, Time, Transform, With};
 use bevy_rapier2d::dynamics::Velocity;
 use bevy::math::{Quat, Vec3};
 use crate::simulation::server_sim::player::components::PlayerInput;
-use crate::simulation::server_sim::player::{Player, PlayerTurret};
+use crate::simulation::Object;
+use crate::simulation::server_sim::config::ServerConfig;
+use crate::simulation::server_sim::player::{DamageTaken, Health, OnAssistEvent, OnDamageDealtEvent, OnDamageEvent, OnHealEvent, OnHealthChangedEvent, OnKillEvent, OnPlayerDeathEvent, Player, PlayerTurret};
 use crate::simulation::server_sim::player::utils::calc_player_next_velocity;
+use crate::simulation::server_sim::spawn::SpawnProtection;
+use crate::utils::commands::despawn::CustomDespawnExt;
+
+/// Seconds between the heals of a regenerating tank.
+const REGEN_INTERVAL: f32 = 0.5;
+/// Fraction of the victim's max health a player has to have dealt to get an assist.
+const ASSIST_THRESHOLD: f32 = 0.25;
 
 pub fn player_move(
     mut query: Query<(&mut Velocity, &Player, &PlayerInput)>,
@@ -30,3 +39,122 @@ pub fn player_turret_rotate(
     });
 }
 
+
+pub fn apply_damage(
+    mut damage_events: EventReader<OnDamageEvent>,
+    (mut kill_writer, mut death_writer): (EventWriter<OnKillEvent>, EventWriter<OnPlayerDeathEvent>),
+    mut dealt_writer: EventWriter<OnDamageDealtEvent>,
+    mut health_writer: EventWriter<OnHealthChangedEvent>,
+    mut healths: Query<(&mut Health, &mut DamageTaken, &Player, &Object, &Transform, Option<&SpawnProtection>)>,
+    players: Query<&Player>,
+    mut commands: Commands,
+) {
+    damage_events.iter().for_each(|e| {
+        let Ok((mut health, mut damage_taken, &Player { id: victim_id, .. }, object, trans, protection)) = healths.get_mut(e.victim) else { return; };
+        // Already dead this frame, don't count the death twice
+        if health.health <= 0. || protection.is_some() { return; }
+
+        let before = health.health;
+        health.health = (health.health - e.damage).clamp(0., health.max_health);
+        if e.damage > 0. {
+            health.since_damage = 0.;
+        }
+
+        health_writer.send(OnHealthChangedEvent {
+            object_id: object.id,
+            health: health.health,
+            max_health: health.max_health,
+        });
+
+        // Damage to yourself or from the map isn't credited to anyone
+        let attacker_id = e.attacker
+            .and_then(|attacker| players.get(attacker).ok())
+            .map(|player| player.id)
+            .filter(|&attacker_id| attacker_id != victim_id);
+
+        if let Some(attacker_id) = attacker_id && before > health.health {
+            let damage = before - health.health;
+            *damage_taken.by_player.entry(attacker_id).or_default() += damage;
+            dealt_writer.send(OnDamageDealtEvent { attacker_id, victim_id, damage });
+        }
+
+        if health.health > 0. { return; }
+
+        commands.entity(e.victim).custom_despawn();
+        death_writer.send(OnPlayerDeathEvent {
+            player_id: victim_id,
+            entity: e.victim,
+            position: trans.translation.truncate(),
+            killer_id: attacker_id,
+            damage_taken: std::mem::take(&mut damage_taken.by_player),
+            max_health: health.max_health,
+        });
+
+        if let Some(attacker_id) = attacker_id {
+            kill_writer.send(OnKillEvent {
+                attacker_id,
+                victim_id,
+            });
+        }
+    });
+}
+
+/// Everyone other than the killer who did at least `ASSIST_THRESHOLD` of the victim's max health
+/// in damage during its life gets an assist.
+pub fn award_assists(
+    mut death_events: EventReader<OnPlayerDeathEvent>,
+    mut assist_writer: EventWriter<OnAssistEvent>,
+) {
+    death_events.iter().for_each(|e| {
+        e.damage_taken.iter()
+            .filter(|(&assister_id, &damage)| Some(assister_id) != e.killer_id &&
+                damage >= e.max_health * ASSIST_THRESHOLD)
+            .for_each(|(&assister_id, _)| {
+                assist_writer.send(OnAssistEvent { assister_id, victim_id: e.player_id });
+            });
+    });
+}
+
+/// Tanks that haven't taken damage for `ServerConfig::regen_delay` seconds heal a little at a
+/// time, so health updates aren't sent every frame.
+pub fn regenerate_health(
+    mut healths: Query<(Entity, &mut Health)>,
+    mut heal_writer: EventWriter<OnHealEvent>,
+    config: Res<ServerConfig>,
+    time: Res<Time>,
+) {
+    let dt = time.delta_seconds();
+    healths.iter_mut().for_each(|(ent, mut health)| {
+        health.since_damage += dt;
+        if config.regen <= 0. || health.since_damage < config.regen_delay || health.health >= health.max_health {
+            health.regen_timer = REGEN_INTERVAL;
+            return;
+        }
+
+        health.regen_timer -= dt;
+        if health.regen_timer <= 0. {
+            health.regen_timer += REGEN_INTERVAL;
+            heal_writer.send(OnHealEvent { target: ent, amount: config.regen * REGEN_INTERVAL });
+        }
+    });
+}
+
+pub fn apply_healing(
+    mut heal_events: EventReader<OnHealEvent>,
+    mut health_writer: EventWriter<OnHealthChangedEvent>,
+    mut healths: Query<(&mut Health, &Object)>,
+) {
+    heal_events.iter().for_each(|e| {
+        let Ok((mut health, object)) = healths.get_mut(e.target) else { return; };
+        // Dead tanks stay dead until they respawn
+        if health.health <= 0. || health.health >= health.max_health { return; }
+
+        health.health = (health.health + e.amount).min(health.max_health);
+
+        health_writer.send(OnHealthChangedEvent {
+            object_id: object.id,
+            health: health.health,
+            max_health: health.max_health,
+        });
+    });
+}
@@ -9,8 +9,9 @@ pub fn calc_player_next_velocity(
     delta_time: f32,
 ) -> Vec2 {
     let new_velocity = curr_vel + (player.accel * input.movement * delta_time);
+    // Tanks going over max speed, from a dash or an explosion, keep their speed and slow down with damping
     if [player.max_speed, curr_vel.length()].iter().all(|v| new_velocity.length() > *v) {
-        new_velocity.clamp_length_max(player.max_speed)
+        new_velocity.clamp_length_max(player.max_speed.max(curr_vel.length()))
     } else {
         new_velocity
     }
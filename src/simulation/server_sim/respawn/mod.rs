@@ -4,7 +4,7 @@ use std::collections::HashMap;
 use crate::AppState;
 use crate::ServerSet::ServerUpdate;
 use crate::simulation::events::OnRespawnTimerFinish;
-use crate::simulation::server_sim::bullet::BulletSystemStage::CollisionHandle;
+use crate::simulation::server_sim::player::PlayerSystemStage::ApplyDamage;
 use crate::utils::networking::messages::PlayerId;
 
 mod systems;
@@ -18,7 +18,7 @@ impl Plugin for RespawnPlugin {
             .add_event::<OnRespawnTimerFinish>()
             .add_systems(
                 (
-                    systems::start_respawn_timer_on_death.after(CollisionHandle),
+                    systems::start_respawn_timer_on_death.after(ApplyDamage),
                     systems::run_respawn_timer,
                     systems::dispatch_respawn_on_countdown,
                 ).chain().in_set(ServerUpdate)
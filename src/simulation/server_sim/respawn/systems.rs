@@ -1,14 +1,17 @@
 use bevy::prelude::{EventReader, EventWriter, Res, ResMut, Time};
 use crate::simulation::events::OnRespawnTimerFinish;
+use crate::simulation::server_sim::game_mode::{ActiveGameMode, RespawnPolicy};
 use crate::simulation::server_sim::player::OnPlayerDeathEvent;
 use crate::simulation::server_sim::respawn::RespawnTimer;
 
 pub fn start_respawn_timer_on_death(
     mut death_reader: EventReader<OnPlayerDeathEvent>,
     mut respawn_timer: ResMut<RespawnTimer>,
+    mode: Option<Res<ActiveGameMode>>,
 ) {
+    let Some(RespawnPolicy::After(delay)) = mode.map(|mode| mode.rules.respawn) else { return; };
     death_reader.iter().for_each(|e| {
-        respawn_timer.map.insert(e.player_id, 5.0);
+        respawn_timer.map.insert(e.player_id, delay);
     });
 }
 
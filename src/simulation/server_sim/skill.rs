@@ -0,0 +1,346 @@
+use std::collections::HashMap;
+use bevy::asset::{AddAsset, AssetLoader, LoadContext, LoadedAsset};
+use bevy::prelude::*;
+use bevy::reflect::TypeUuid;
+use bevy::utils::BoxedFuture;
+use bevy_rapier2d::prelude::{ExternalImpulse, Velocity};
+use serde::{Deserialize, Serialize};
+use crate::asset_loader::resources::SkillAssets;
+use crate::ServerSet::ServerUpdate;
+use crate::simulation::ObjectId;
+use crate::simulation::server_sim::bullet::BulletSystemStage::CollisionHandle;
+use crate::simulation::server_sim::player::{Health, OnHealEvent, Player, PlayerInput, PlayerTurret};
+
+/// Every tank has this many skill slots, each bound to its own input.
+pub const SKILL_SLOTS: usize = 4;
+/// Equipped in the first slots of every tank when it spawns, so they have to be defined.
+pub const DEFAULT_SKILLS: [&str; 3] = ["boost", "dash", "shield"];
+/// Energy of a freshly equipped `Held` skill.
+pub const MAX_ENERGY: f32 = 1.;
+
+/// Registers the skill definitions asset, loaded from `assets/skills/default.skills.ron` with
+/// the other assets, and applies the effects of equipped skills on the server.
+pub struct SkillPlugin;
+
+impl Plugin for SkillPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_asset::<SkillDefinitions>()
+            .init_asset_loader::<SkillDefinitionsLoader>()
+            .add_systems(
+                (
+                    equip_default_skills.before(update_skills),
+                    update_skills.before(CollisionHandle),
+                ).in_set(ServerUpdate)
+            );
+    }
+}
+
+/// Every skill by name. New skills only need an entry in the definitions file, as long as
+/// their effect already exists.
+#[derive(Debug, Clone, Serialize, Deserialize, TypeUuid)]
+#[uuid = "9c4e2a71-3f5d-4b8e-a6c2-1d7f0e8b5a34"]
+pub struct SkillDefinitions {
+    pub skills: HashMap<String, SkillDefinition>,
+}
+
+impl SkillDefinitions {
+    pub fn get(&self, name: &str) -> Option<&SkillDefinition> {
+        self.skills.get(name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SkillDefinition {
+    pub display_name: String,
+    pub kind: SkillKind,
+    pub effect: SkillEffect,
+}
+
+/// How a skill is used.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum SkillKind {
+    /// Goes off when its input is pressed, then can't be used again for `cooldown` seconds.
+    Active { cooldown: f32 },
+    /// On while its input is held, draining energy that regenerates while it's off.
+    Held {
+        /// Energy lost per second, out of 1.
+        drain: f32,
+        /// Energy gained per second while off.
+        regen: f32,
+        /// Energy needed before it can be turned back on.
+        min_energy: f32,
+    },
+    /// Always on.
+    Passive,
+}
+
+/// What a skill does once it's on.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum SkillEffect {
+    /// Raises the tank's top speed and acceleration for `duration` seconds.
+    Boost { duration: f32, speed_multiplier: f32, accel_multiplier: f32 },
+    /// Pushes the tank towards where it's driving, or where it's already going if it isn't.
+    Dash { impulse: f32 },
+    /// Blocks hits coming from within `arc` degrees of the turret. Blocking drains
+    /// `drain_per_damage` energy per point of damage on top of holding it up.
+    Shield { arc: f32, drain_per_damage: f32 },
+    /// The tank's bullets ricochet off trees and walls this many more times.
+    BouncingBullets { bounces: u32, speed_loss: f32 },
+    /// Heals the tank straight away, can't be used at full health.
+    Repair { amount: f32 },
+}
+
+/// A skill equipped in a slot, with everything the server needs to simulate it.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SkillSlot {
+    /// Name of the skill in the `SkillDefinitions`.
+    pub name: String,
+    pub definition: SkillDefinition,
+    /// Seconds until an `Active` skill can be used again.
+    pub cooldown: f32,
+    /// Seconds left on an effect that lasts, like a boost.
+    pub remaining: f32,
+    /// Energy of a `Held` skill, from 0 to `MAX_ENERGY`.
+    pub energy: f32,
+    /// Whether a `Held` or `Passive` skill is on, or a lasting effect is running.
+    pub active: bool,
+}
+
+impl SkillSlot {
+    pub fn new(name: &str, definition: &SkillDefinition) -> Self {
+        SkillSlot {
+            name: name.to_owned(),
+            definition: definition.clone(),
+            cooldown: 0.,
+            remaining: 0.,
+            energy: MAX_ENERGY,
+            active: false,
+        }
+    }
+}
+
+/// The skills a tank has equipped, the index of a slot decides which input uses it.
+#[derive(Component, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SkillLoadout {
+    pub slots: [Option<SkillSlot>; SKILL_SLOTS],
+    /// Where the turret is aiming, which is where shields face.
+    pub aim: Vec2,
+}
+
+impl Default for SkillLoadout {
+    fn default() -> Self {
+        SkillLoadout {
+            slots: Default::default(),
+            aim: Vec2::X,
+        }
+    }
+}
+
+impl SkillLoadout {
+    /// Puts the skill in the first free slot. Returns false if it's already equipped or every
+    /// slot is taken.
+    pub fn equip(&mut self, name: &str, definition: &SkillDefinition) -> bool {
+        if self.slots.iter().flatten().any(|slot| slot.name == name) { return false; }
+        let Some(free) = self.slots.iter_mut().find(|slot| slot.is_none()) else { return false; };
+        *free = Some(SkillSlot::new(name, definition));
+        true
+    }
+
+    fn active_shield(&mut self) -> Option<(&mut SkillSlot, f32)> {
+        self.slots.iter_mut().flatten().find_map(|slot| {
+            let effect = slot.definition.effect;
+            match effect {
+                SkillEffect::Shield { drain_per_damage, .. } if slot.active => Some((slot, drain_per_damage)),
+                _ => None,
+            }
+        })
+    }
+
+    /// The arc in degrees of the raised shield, if there is one.
+    pub fn shield_arc(&self) -> Option<f32> {
+        self.slots.iter().flatten().find_map(|slot| match slot.definition.effect {
+            SkillEffect::Shield { arc, .. } if slot.active => Some(arc),
+            _ => None,
+        })
+    }
+
+    /// Whether a raised shield faces a hit coming from `from`, relative to the tank.
+    pub fn shield_blocks(&self, from: Vec2) -> bool {
+        let Some(arc) = self.shield_arc() else { return false; };
+        from != Vec2::ZERO && self.aim.angle_between(from).abs() <= arc.to_radians() / 2.
+    }
+
+    /// Drains the raised shield for a hit it blocked.
+    pub fn block(&mut self, damage: f32) {
+        let Some((slot, drain_per_damage)) = self.active_shield() else { return; };
+        slot.energy = (slot.energy - damage * drain_per_damage).max(0.);
+        if slot.energy == 0. {
+            slot.active = false;
+        }
+    }
+
+    /// Extra bounces and the speed lost on each of them given to the tank's bullets by
+    /// passive skills, if it has any.
+    pub fn bullet_bounces(&self) -> Option<(u32, f32)> {
+        self.slots.iter().flatten()
+            .filter(|slot| slot.active)
+            .filter_map(|slot| match slot.definition.effect {
+                SkillEffect::BouncingBullets { bounces, speed_loss } => Some((bounces, speed_loss)),
+                _ => None,
+            })
+            .reduce(|(bounces, loss), (more, other_loss)| (bounces + more, loss.max(other_loss)))
+    }
+}
+
+/// What the HUD shows about your skills, only sent to the tank's owner.
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SkillStatus {
+    pub loadout: SkillLoadout,
+}
+
+/// Where a raised shield faces and how wide it is in degrees.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ShieldState {
+    pub direction: Vec2,
+    pub arc: f32,
+}
+
+/// Raised shields, sent to every client so they can draw them.
+#[derive(Resource, Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ActiveShields {
+    pub shields: bevy::utils::HashMap<ObjectId, ShieldState>,
+}
+
+fn equip_default_skills(
+    mut loadouts: Query<&mut SkillLoadout, Added<SkillLoadout>>,
+    skill_assets: Res<SkillAssets>,
+    definitions: Res<Assets<SkillDefinitions>>,
+) {
+    loadouts.iter_mut().for_each(|mut loadout| {
+        DEFAULT_SKILLS.iter().for_each(|&name| {
+            match skill_assets.get(&definitions, name) {
+                Some(definition) => { loadout.equip(name, definition); }
+                None => warn!("Default skill '{name}' isn't defined"),
+            }
+        });
+    });
+}
+
+/// Ticks the cooldowns and energy of every equipped skill, and uses the ones whose input is
+/// held once the server allows it. Holding the input of an `Active` skill uses it again as
+/// soon as it's recharged.
+fn update_skills(
+    mut players: Query<(Entity, &mut SkillLoadout, &mut Player, &mut ExternalImpulse, &Velocity, &PlayerInput, &Health, &Children)>,
+    turrets: Query<&PlayerTurret>,
+    mut heal_writer: EventWriter<OnHealEvent>,
+    time: Res<Time>,
+) {
+    let dt = time.delta_seconds();
+    players.iter_mut().for_each(|(ent, mut loadout, mut player, mut impulse, vel, input, health, children)| {
+        if let Some(turret) = children.iter().find_map(|&child| turrets.get(child).ok()) &&
+            turret.direction != Vec2::ZERO {
+            loadout.aim = turret.direction;
+        }
+
+        loadout.slots.iter_mut().zip(input.skills).for_each(|(slot, pressed)| {
+            let Some(slot) = slot else { return; };
+            match slot.definition.kind {
+                SkillKind::Active { cooldown } => {
+                    slot.cooldown = (slot.cooldown - dt).max(0.);
+                    if slot.active {
+                        slot.remaining -= dt;
+                        if slot.remaining <= 0. {
+                            end_effect(slot, &mut player);
+                        }
+                    } else if pressed && slot.cooldown <= 0. &&
+                        start_effect(slot, &mut player, &mut impulse, vel, input, health) {
+                        slot.cooldown = cooldown;
+                        if let SkillEffect::Repair { amount } = slot.definition.effect {
+                            heal_writer.send(OnHealEvent { target: ent, amount });
+                        }
+                    }
+                }
+                SkillKind::Held { drain, regen, min_energy } => {
+                    let can_turn_on = slot.active || slot.energy >= min_energy;
+                    if pressed && can_turn_on && slot.energy > 0. {
+                        slot.active = true;
+                        slot.energy = (slot.energy - drain * dt).max(0.);
+                        if slot.energy == 0. {
+                            slot.active = false;
+                        }
+                    } else {
+                        slot.active = false;
+                        slot.energy = (slot.energy + regen * dt).min(MAX_ENERGY);
+                    }
+                }
+                SkillKind::Passive => slot.active = true,
+            }
+        });
+    });
+}
+
+/// Applies the effect of an `Active` skill. Returns false if it couldn't be used, so it isn't
+/// put on cooldown.
+fn start_effect(
+    slot: &mut SkillSlot,
+    player: &mut Player,
+    impulse: &mut ExternalImpulse,
+    vel: &Velocity,
+    input: &PlayerInput,
+    health: &Health,
+) -> bool {
+    match slot.definition.effect {
+        SkillEffect::Boost { duration, speed_multiplier, accel_multiplier } => {
+            slot.active = true;
+            slot.remaining = duration;
+            player.max_speed *= speed_multiplier;
+            player.accel *= accel_multiplier;
+            true
+        }
+        SkillEffect::Dash { impulse: strength } => {
+            let direction = if input.movement != Vec2::ZERO {
+                input.movement.normalize_or_zero()
+            } else {
+                vel.linvel.normalize_or_zero()
+            };
+            if direction == Vec2::ZERO { return false; }
+            impulse.impulse += direction * strength;
+            true
+        }
+        // The healing itself is sent by the caller, it goes through `OnHealEvent`
+        SkillEffect::Repair { .. } => health.health < health.max_health,
+        SkillEffect::Shield { .. } | SkillEffect::BouncingBullets { .. } => false,
+    }
+}
+
+/// Puts back what a lasting effect changed once it runs out.
+fn end_effect(slot: &mut SkillSlot, player: &mut Player) {
+    slot.active = false;
+    slot.remaining = 0.;
+    if let SkillEffect::Boost { speed_multiplier, accel_multiplier, .. } = slot.definition.effect {
+        player.max_speed /= speed_multiplier;
+        player.accel /= accel_multiplier;
+    }
+}
+
+#[derive(Default)]
+pub struct SkillDefinitionsLoader;
+
+impl AssetLoader for SkillDefinitionsLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
+        Box::pin(async move {
+            let definitions = ron::de::from_bytes::<SkillDefinitions>(bytes)?;
+            load_context.set_default_asset(LoadedAsset::new(definitions));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["skills.ron"]
+    }
+}
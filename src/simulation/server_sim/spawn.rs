@@ -1,12 +1,18 @@
 use std::collections::hash_map::Entry::Vacant;
 use bevy::app::App;
-use bevy::prelude::{Commands, Component, EventReader, EventWriter, GlobalTransform, IntoSystemConfig, Plugin, Query, ResMut, With};
-use bevy::log::info;
+use bevy::math::Vec2;
+use bevy::prelude::{Commands, Component, Entity, EventReader, EventWriter, GlobalTransform, IntoSystemAppConfig, IntoSystemConfig, IntoSystemConfigs, OnExit, Plugin, Query, Res, ResMut, Resource, Time, Transform, With};
+use bevy::log::{info, warn};
 use bevy::hierarchy::BuildChildren;
 use bevy::utils::HashSet;
+use bevy_rapier2d::prelude::{QueryFilter, RapierContext};
+use crate::AppState;
 use crate::ServerSet::ServerUpdate;
 use crate::simulation::events::{OnPlayerConnectEvent, OnPlayerSpawnEvent, OnRespawnTimerFinish};
-use crate::simulation::server_sim::player::Player;
+use crate::simulation::server_sim::game_mode::{ActiveGameMode, SpawnContext};
+use crate::simulation::map::MapEntity;
+use crate::simulation::server_sim::player::{OnDamageEvent, OnPlayerDeathEvent, Player};
+use crate::simulation::server_sim::weapon::DEFAULT_WEAPON;
 use crate::simulation::{Object, PlayerData, SyncedObjects};
 use crate::simulation::Lobby;
 use crate::simulation::server_sim::init::OnInitEvent;
@@ -18,18 +24,60 @@ pub struct SpawnPlugin;
 impl Plugin for SpawnPlugin {
     fn build(&self, app: &mut App) {
         app
+            .init_resource::<RecentCombat>()
             .add_systems(
                 (
                     lobby_players_on_connect.before(spawn_player_system),
                     spawn_player_system.in_set(ServerUpdate)
                 )
-            );
+            )
+            .add_systems(
+                (
+                    track_recent_combat.before(spawn_player_system),
+                    tick_spawn_protection,
+                ).in_set(ServerUpdate)
+            )
+            .add_system(clear_recent_combat.in_schedule(OnExit(AppState::InGame)));
     }
 }
 
+/// Seconds a tank can't be damaged for after spawning, unless it fires first.
+const SPAWN_PROTECTION_TIME: f32 = 3.;
+/// Seconds fighting somewhere keeps making spawn points nearby less safe.
+pub const RECENT_COMBAT_TIME: f32 = 10.;
+/// Fighting this close to an earlier spot is counted as the same spot.
+const COMBAT_SPOT_RADIUS: f32 = 150.;
+
 #[derive(Component)]
 pub struct SpawnPoint;
 
+/// Keeps a freshly spawned tank from taking damage, removed once it runs out or the tank fires.
+#[derive(Component)]
+pub struct SpawnProtection {
+    pub remaining: f32,
+}
+
+/// Where tanks recently took damage or died, so players aren't spawned into a fight.
+#[derive(Resource, Default)]
+pub struct RecentCombat {
+    pub spots: Vec<CombatSpot>,
+}
+
+pub struct CombatSpot {
+    pub position: Vec2,
+    /// Seconds since the last damage here.
+    pub age: f32,
+}
+
+impl RecentCombat {
+    fn add(&mut self, position: Vec2) {
+        match self.spots.iter_mut().find(|spot| spot.position.distance(position) < COMBAT_SPOT_RADIUS) {
+            Some(spot) => spot.age = 0.,
+            None => self.spots.push(CombatSpot { position, age: 0. }),
+        }
+    }
+}
+
 
 pub fn lobby_players_on_connect(
     mut join_events: EventReader<OnPlayerConnectEvent>,
@@ -49,36 +97,59 @@ pub fn spawn_player_system(
     mut init_events: EventReader<OnInitEvent>,
     mut spawn_writer: EventWriter<OnPlayerSpawnEvent>,
     spawn_points: Query<&GlobalTransform, With<SpawnPoint>>,
-    players: Query<&GlobalTransform, With<Player>>,
+    players: Query<(&Player, &GlobalTransform)>,
+    (rapier_context, obstacles, recent_combat): (Res<RapierContext>, Query<(), With<MapEntity>>, Res<RecentCombat>),
     mut commands: Commands,
     mut lobby: ResMut<Lobby>,
     mut objects: ResMut<SyncedObjects>,
+    mode: Option<Res<ActiveGameMode>>,
 ) {
+    let Some(mode) = mode else { return; };
     let events =
-        join_events.iter().map(|e| e.player_id)
+        join_events.iter().filter(|_| !mode.spawns_locked).map(|e| e.player_id)
             .chain(respawn_events.iter().map(|e| e.player_id))
             .chain(init_events.iter().flat_map(|_|
                 lobby.player_data.iter().map(|(&id, _)| id).collect::<Vec<PlayerId>>()
             )).collect::<HashSet<PlayerId>>();
 
+    let is_obstacle = |entity| obstacles.get(entity).is_ok();
+    let filter = QueryFilter::new().exclude_sensors().predicate(&is_obstacle);
+    let line_of_sight = |from: Vec2, to: Vec2| {
+        let toward = to - from;
+        rapier_context.cast_ray(from, toward.normalize_or_zero(), toward.length(), true, filter).is_none()
+    };
+
     events.iter().for_each(|&player_id| {
         info!("Player {} Spawned", player_id);
 
-        let spawn_position = spawn_points.iter().map(|spawn_trans| {
-            let spawn_trunc = spawn_trans.translation().truncate();
-            (players.iter().map(|player_trans|
-                (spawn_trunc - player_trans.translation().truncate()).length())
-                 .sum::<f32>(),
-             spawn_trunc)
-        }).max_by(|(x, _), (y, _)| x.total_cmp(y)).unwrap().1;
+        let context = SpawnContext {
+            player_id,
+            team: lobby.team_of(player_id),
+            teams: mode.teams.clone(),
+            spawn_points: spawn_points.iter().map(|trans| trans.translation().truncate()).collect(),
+            players: players.iter()
+                .map(|(player, trans)| (player.id, lobby.team_of(player.id), trans.translation().truncate()))
+                .collect(),
+            recent_combat: recent_combat.spots.iter().map(|spot| (spot.position, spot.age)).collect(),
+            line_of_sight: &line_of_sight,
+            score_spawn: mode.rules.score_spawn,
+        };
+        let Some(spawn_position) = (mode.rules.select_spawn)(&context) else {
+            warn!("No spawn point for Player {}", player_id);
+            return;
+        };
+
+        let weapon = lobby.player_data.get(&player_id)
+            .and_then(|data| data.weapon.clone())
+            .unwrap_or_else(|| DEFAULT_WEAPON.to_owned());
 
         let new_object = Object::new();
 
         let player_entity = commands.spawn(
             get_player_bundle(player_id, Some(spawn_position)))
-            .insert(new_object)
+            .insert((new_object, SpawnProtection { remaining: SPAWN_PROTECTION_TIME }))
             .with_children(|p| {
-                p.spawn(get_turret_bundle());
+                p.spawn(get_turret_bundle(&weapon));
             }).id();
 
         objects.objects.insert(new_object.id, player_entity);
@@ -92,3 +163,38 @@ pub fn spawn_player_system(
         })
     });
 }
+
+fn track_recent_combat(
+    mut damage_events: EventReader<OnDamageEvent>,
+    mut death_events: EventReader<OnPlayerDeathEvent>,
+    tanks: Query<&Transform>,
+    mut recent_combat: ResMut<RecentCombat>,
+    time: Res<Time>,
+) {
+    recent_combat.spots.iter_mut().for_each(|spot| spot.age += time.delta_seconds());
+    recent_combat.spots.retain(|spot| spot.age < RECENT_COMBAT_TIME);
+
+    damage_events.iter().for_each(|e| {
+        if let Ok(trans) = tanks.get(e.victim) {
+            recent_combat.add(trans.translation.truncate());
+        }
+    });
+    death_events.iter().for_each(|e| recent_combat.add(e.position));
+}
+
+fn tick_spawn_protection(
+    mut protected: Query<(Entity, &mut SpawnProtection)>,
+    mut commands: Commands,
+    time: Res<Time>,
+) {
+    protected.iter_mut().for_each(|(ent, mut protection)| {
+        protection.remaining -= time.delta_seconds();
+        if protection.remaining <= 0. {
+            commands.entity(ent).remove::<SpawnProtection>();
+        }
+    });
+}
+
+fn clear_recent_combat(mut recent_combat: ResMut<RecentCombat>) {
+    recent_combat.spots.clear();
+}
@@ -0,0 +1,281 @@
+use std::collections::HashMap;
+use bevy::app::App;
+use bevy::asset::{AddAsset, AssetLoader, LoadContext, LoadedAsset};
+use bevy::log::{info, warn};
+use bevy::prelude::{Assets, EventReader, EventWriter, IntoSystemConfig, Plugin, Res, ResMut, Resource};
+use bevy::reflect::TypeUuid;
+use bevy::utils::BoxedFuture;
+use serde::{Deserialize, Serialize};
+use crate::asset_loader::components::SpriteEnum;
+use crate::asset_loader::resources::WeaponAssets;
+use crate::ServerSet::ServerUpdate;
+use crate::simulation::Lobby;
+use crate::simulation::events::OnPlayerDataChangedEvent;
+use crate::utils::networking::messages::PlayerId;
+
+/// Registers the weapon definitions asset, loaded from `assets/weapons/default.weapons.ron`
+/// with the other assets, and lets players pick which one they spawn with.
+pub struct WeaponPlugin;
+
+impl Plugin for WeaponPlugin {
+    fn build(&self, app: &mut App) {
+        app
+            .add_asset::<WeaponDefinitions>()
+            .init_asset_loader::<WeaponDefinitionsLoader>()
+            .add_event::<OnWeaponChoiceEvent>()
+            .add_system(choose_weapon.in_set(ServerUpdate));
+    }
+}
+
+/// Turrets are given this weapon when they spawn, so it has to be defined.
+pub const DEFAULT_WEAPON: &str = "machine_gun";
+
+/// Sent by a client picking the weapon for their next spawn.
+pub struct OnWeaponChoiceEvent {
+    pub player_id: PlayerId,
+    pub weapon: String,
+}
+
+/// Every weapon by name. New weapons only need an entry in the definitions file.
+#[derive(Debug, Clone, Serialize, Deserialize, TypeUuid)]
+#[uuid = "5b0f3c1e-8d2a-4e67-9a41-6c3e2f7d9b10"]
+pub struct WeaponDefinitions {
+    pub weapons: HashMap<String, WeaponDefinition>,
+}
+
+impl WeaponDefinitions {
+    pub fn get(&self, name: &str) -> Option<&WeaponDefinition> {
+        self.weapons.get(name)
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WeaponDefinition {
+    pub display_name: String,
+    pub damage: f32,
+    pub speed: f32,
+    /// Degrees. Several pellets are fanned out evenly across it, a single one is fired
+    /// at a random angle within it.
+    pub spread: f32,
+    pub pellets: u32,
+    /// Shots per second.
+    pub fire_rate: f32,
+    /// Seconds before a bullet disappears.
+    pub lifetime: f32,
+    pub sprite: SpriteEnum,
+    pub scale: f32,
+    pub collider_radius: f32,
+    /// How many times bullets ricochet off trees and walls, they're destroyed by them otherwise.
+    #[serde(default)]
+    pub bounces: u32,
+    /// Fraction of speed lost on every bounce.
+    #[serde(default)]
+    pub bounce_speed_loss: f32,
+    /// Bullets with an explosion blow up when they hit something or run out of lifetime.
+    #[serde(default)]
+    pub explosion: Option<ExplosionDefinition>,
+    /// Hitscan weapons hit instantly along a ray instead of firing bullets, so the bullet
+    /// settings other than damage, spread and pellets don't apply to them.
+    #[serde(default)]
+    pub hitscan: Option<HitscanDefinition>,
+    /// Homing bullets lock onto an enemy in front of them and steer towards it.
+    #[serde(default)]
+    pub homing: Option<HomingDefinition>,
+    /// Weapons with a magazine have to reload once it's empty, or when the player reloads early.
+    #[serde(default)]
+    pub magazine: Option<MagazineDefinition>,
+    /// Weapons with heat overheat when fired for too long, and can't fire until they've cooled down.
+    #[serde(default)]
+    pub heat: Option<HeatDefinition>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct ExplosionDefinition {
+    pub radius: f32,
+    /// Damage at the center, falling off to nothing at the edge of the radius.
+    pub damage: f32,
+    /// Impulse at the center, falling off the same way.
+    pub knockback: f32,
+    #[serde(default)]
+    pub damages_owner: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HitscanDefinition {
+    pub range: f32,
+    /// Whether the ray carries on through tanks, it always stops at trees and walls.
+    #[serde(default)]
+    pub pierce: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HomingDefinition {
+    /// Degrees either side of the turret's aim that it can lock on within.
+    pub lock_angle: f32,
+    pub lock_range: f32,
+    /// Degrees per second.
+    pub turn_rate: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct MagazineDefinition {
+    pub size: u32,
+    /// Seconds.
+    pub reload_time: f32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct HeatDefinition {
+    /// Heat added by every shot, the weapon overheats at 1.
+    pub per_shot: f32,
+    /// Heat lost per second.
+    pub cooling: f32,
+}
+
+impl WeaponDefinition {
+    /// Angles in radians off the aim direction for every pellet of a shot, `random` is from 0 to 1.
+    pub fn pellet_angles(&self, random: f32) -> Vec<f32> {
+        let spread = self.spread.to_radians();
+        match self.pellets {
+            0 => Vec::new(),
+            1 => vec![(random - 0.5) * spread],
+            pellets => (0..pellets)
+                .map(|i| (i as f32 / (pellets - 1) as f32 - 0.5) * spread)
+                .collect(),
+        }
+    }
+}
+
+/// Ammo and heat of a turret's weapon, simulated on the server.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct WeaponState {
+    /// `None` until the weapon is first fired, which means a full magazine.
+    pub ammo: Option<u32>,
+    /// Seconds left on the reload.
+    pub reloading: Option<f32>,
+    pub heat: f32,
+    /// Overheated weapons can't fire until they've cooled down completely.
+    pub overheated: bool,
+}
+
+impl WeaponState {
+    pub fn tick(&mut self, weapon: &WeaponDefinition, delta_seconds: f32) {
+        if let Some(remaining) = &mut self.reloading {
+            *remaining -= delta_seconds;
+            if *remaining <= 0. {
+                self.reloading = None;
+                self.ammo = None;
+            }
+        }
+
+        if let Some(heat) = weapon.heat {
+            self.heat = (self.heat - heat.cooling * delta_seconds).max(0.);
+            if self.heat == 0. {
+                self.overheated = false;
+            }
+        }
+    }
+
+    /// Rounds left in the magazine, if the weapon has one.
+    pub fn ammo(&self, weapon: &WeaponDefinition) -> Option<u32> {
+        weapon.magazine.map(|magazine| self.ammo.unwrap_or(magazine.size))
+    }
+
+    pub fn can_fire(&self, weapon: &WeaponDefinition) -> bool {
+        self.reloading.is_none() && !self.overheated && self.ammo(weapon) != Some(0)
+    }
+
+    /// Does nothing if the weapon has no magazine, or it's already reloading or full.
+    pub fn start_reload(&mut self, weapon: &WeaponDefinition) {
+        let Some(magazine) = weapon.magazine else { return; };
+        if self.reloading.is_none() && self.ammo(weapon) != Some(magazine.size) {
+            self.reloading = Some(magazine.reload_time);
+        }
+    }
+
+    pub fn fired(&mut self, weapon: &WeaponDefinition) {
+        if let Some(ammo) = self.ammo(weapon) {
+            self.ammo = Some(ammo.saturating_sub(1));
+            if ammo <= 1 {
+                self.start_reload(weapon);
+            }
+        }
+
+        if let Some(heat) = weapon.heat {
+            self.heat += heat.per_shot;
+            if self.heat >= 1. {
+                self.heat = 1.;
+                self.overheated = true;
+            }
+        }
+    }
+
+    pub fn status(&self, weapon: &WeaponDefinition) -> WeaponStatus {
+        WeaponStatus {
+            display_name: weapon.display_name.clone(),
+            ammo: weapon.magazine.zip(self.ammo(weapon)).map(|(magazine, ammo)| (ammo, magazine.size)),
+            reload_progress: weapon.magazine.zip(self.reloading)
+                .map(|(magazine, remaining)| 1. - remaining / magazine.reload_time),
+            heat: weapon.heat.map(|_| self.heat),
+            overheated: self.overheated,
+        }
+    }
+}
+
+/// What the HUD shows about your weapon, only sent to the tank's owner.
+#[derive(Resource, Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct WeaponStatus {
+    pub display_name: String,
+    /// Rounds left and magazine size.
+    pub ammo: Option<(u32, u32)>,
+    /// From 0 to 1.
+    pub reload_progress: Option<f32>,
+    /// From 0 to 1.
+    pub heat: Option<f32>,
+    pub overheated: bool,
+}
+
+/// Keeps the player's pick in their `PlayerData` for their next spawn. Names that aren't defined
+/// get them the default weapon instead.
+fn choose_weapon(
+    mut choice_events: EventReader<OnWeaponChoiceEvent>,
+    weapon_assets: Res<WeaponAssets>,
+    definitions: Res<Assets<WeaponDefinitions>>,
+    mut lobby: ResMut<Lobby>,
+    mut changed_writer: EventWriter<OnPlayerDataChangedEvent>,
+) {
+    choice_events.iter().for_each(|e| {
+        let weapon = if weapon_assets.get(&definitions, &e.weapon).is_some() {
+            e.weapon.clone()
+        } else {
+            warn!("Player {} picked unknown weapon '{}', using '{}'", e.player_id, e.weapon, DEFAULT_WEAPON);
+            DEFAULT_WEAPON.to_owned()
+        };
+
+        let Some(data) = lobby.player_data.get_mut(&e.player_id) else { return; };
+        info!("Player {} will spawn with '{}'", e.player_id, weapon);
+        data.weapon = Some(weapon);
+        changed_writer.send(OnPlayerDataChangedEvent { player_id: e.player_id });
+    });
+}
+
+#[derive(Default)]
+pub struct WeaponDefinitionsLoader;
+
+impl AssetLoader for WeaponDefinitionsLoader {
+    fn load<'a>(
+        &'a self,
+        bytes: &'a [u8],
+        load_context: &'a mut LoadContext,
+    ) -> BoxedFuture<'a, Result<(), bevy::asset::Error>> {
+        Box::pin(async move {
+            let definitions = ron::de::from_bytes::<WeaponDefinitions>(bytes)?;
+            load_context.set_default_asset(LoadedAsset::new(definitions));
+            Ok(())
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["weapons.ron"]
+    }
+}
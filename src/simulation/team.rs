@@ -0,0 +1,85 @@
+use bevy::app::App;
+use bevy::prelude::{Children, Plugin, Query, Res, With, Without};
+use serde::{Deserialize, Serialize};
+use crate::asset_loader::components::SpriteEnum;
+use crate::simulation::Lobby;
+use crate::simulation::server_sim::player::{Player, PlayerTurret};
+
+/// Paints tanks in their team's colors, on the server and on clients.
+pub struct TeamPlugin;
+
+impl Plugin for TeamPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_system(apply_team_sprites);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum Team {
+    Brown,
+    Grey,
+    Green,
+}
+
+impl Team {
+    pub const ALL: [Team; 3] = [Team::Brown, Team::Grey, Team::Green];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Team::Brown => "Brown",
+            Team::Grey => "Grey",
+            Team::Green => "Green",
+        }
+    }
+
+    pub fn tank_sprite(self) -> SpriteEnum {
+        match self {
+            Team::Brown => SpriteEnum::TankDefault,
+            Team::Grey => SpriteEnum::TankClassic,
+            Team::Green => SpriteEnum::TankGreen,
+        }
+    }
+
+    pub fn turret_sprite(self) -> SpriteEnum {
+        match self {
+            Team::Brown => SpriteEnum::TankDefaultTurret,
+            Team::Grey => SpriteEnum::TankClassicTurret,
+            Team::Green => SpriteEnum::TankGreenTurret,
+        }
+    }
+
+    pub fn flag_sprite(self) -> SpriteEnum {
+        match self {
+            Team::Brown => SpriteEnum::FlagBrown,
+            Team::Grey => SpriteEnum::FlagGrey,
+            Team::Green => SpriteEnum::FlagGreen,
+        }
+    }
+
+    /// Which team's flag a replicated sprite is, clients only see flags as sprites.
+    pub fn of_flag_sprite(sprite: SpriteEnum) -> Option<Team> {
+        Team::ALL.into_iter().find(|team| team.flag_sprite() == sprite)
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn apply_team_sprites(
+    lobby: Res<Lobby>,
+    mut tank_q: Query<(&Player, &mut SpriteEnum, Option<&Children>), Without<PlayerTurret>>,
+    mut turret_q: Query<&mut SpriteEnum, (With<PlayerTurret>, Without<Player>)>,
+) {
+    tank_q.iter_mut().for_each(|(player, mut sprite, children)| {
+        let Some(team) = lobby.team_of(player.id) else { return; };
+
+        if *sprite != team.tank_sprite() {
+            *sprite = team.tank_sprite();
+        }
+
+        children.into_iter().flatten().for_each(|&child| {
+            let Ok(mut turret_sprite) = turret_q.get_mut(child) else { return; };
+            if *turret_sprite != team.turret_sprite() {
+                *turret_sprite = team.turret_sprite();
+            }
+        });
+    });
+}
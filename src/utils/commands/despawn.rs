@@ -3,6 +3,7 @@ use bevy::prelude::{Entity, World};
 use bevy_quinnet::server::Server;
 use bevy::hierarchy::Children;
 use crate::simulation::events::OnObjectDespawnEvent;
+use crate::simulation::server_sim::mine::Mine;
 use crate::simulation::server_sim::player::Player;
 use crate::simulation::{Object, SyncedObjects};
 use crate::simulation::Lobby;
@@ -36,7 +37,8 @@ fn custom_despawn(world: &mut World, entity: Entity) {
         objects.objects.remove(&object.id);
 
         if world.get_resource::<Server>().is_some() {
-            world.send_event::<OnObjectDespawnEvent>(OnObjectDespawnEvent { id: object.id });
+            let mine_owner = world.get::<Mine>(entity).map(|mine| mine.owner_id);
+            world.send_event::<OnObjectDespawnEvent>(OnObjectDespawnEvent { id: object.id, mine_owner });
         }
     }
 
@@ -30,7 +30,7 @@ pub fn filter_points_by_min_distance(points: Vec<Vec2>, min_distance: f32) -> Ve
     filtered_points
 }
 
-pub fn generate_random_points_in_polygon<T: Into<Vec2> + Copy>(polygon: &[T], count: usize) -> Vec<Vec2> {
+pub fn generate_random_points_in_polygon<T: Into<Vec2> + Copy>(polygon: &[T], count: usize, seed: u64) -> Vec<Vec2> {
     let mut points = Vec::new();
 
     // Find the bounding box of the polygon
@@ -57,7 +57,7 @@ pub fn generate_random_points_in_polygon<T: Into<Vec2> + Copy>(polygon: &[T], co
 
 
     // Set up random number generator and distributions
-    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
 
     while points.len() < count {
         let x = x_dist.sample(&mut rng);
@@ -121,10 +121,10 @@ pub fn generate_evenly_spaced_points_on_polygon_edges<T: Into<Vec2> + Copy>(poly
     points
 }
 
-pub fn nudge_points_randomly(points: Vec<Vec2>, nudge_amount: f32) -> Vec<Vec2> {
+pub fn nudge_points_randomly(points: Vec<Vec2>, nudge_amount: f32, seed: u64) -> Vec<Vec2> {
     let mut new_points = Vec::new();
 
-    let mut rng = ChaCha8Rng::seed_from_u64(0);
+    let mut rng = ChaCha8Rng::seed_from_u64(seed);
 
     points.iter().for_each(|&p| {
         let x_push = rng.gen_range(-nudge_amount..=nudge_amount);
@@ -137,7 +137,9 @@ pub fn nudge_points_randomly(points: Vec<Vec2>, nudge_amount: f32) -> Vec<Vec2>
     new_points
 }
 
-fn is_point_in_polygon<T: Into<Vec2> + Copy>(point: &Vec2, polygon: &[T]) -> bool {
+pub fn is_point_in_polygon<T: Into<Vec2> + Copy>(point: &Vec2, polygon: &[T]) -> bool {
+    if polygon.len() < 3 { return false; }
+
     let mut is_inside = false;
     let mut i = 0;
     let mut j = polygon.len() - 1;
@@ -155,4 +157,15 @@ fn is_point_in_polygon<T: Into<Vec2> + Copy>(point: &Vec2, polygon: &[T]) -> boo
     }
 
     is_inside
-}
\ No newline at end of file
+}
+
+/// Shortest distance from a point to any edge of a closed polygon.
+pub fn distance_to_polygon_edges<T: Into<Vec2> + Copy>(point: Vec2, polygon: &[T]) -> f32 {
+    (0..polygon.len()).map(|i| {
+        let start = polygon[i].into();
+        let end = polygon[(i + 1) % polygon.len()].into();
+        let edge = end - start;
+        let t = ((point - start).dot(edge) / edge.length_squared()).clamp(0., 1.);
+        point.distance(start + edge * t)
+    }).fold(f32::MAX, f32::min)
+}
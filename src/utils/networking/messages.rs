@@ -3,7 +3,17 @@ use bevy::prelude::{Vec2, Quat, Transform};
 use bevy::utils::HashMap;
 use crate::asset_loader::components::SpriteEnum;
 use crate::simulation::ObjectId;
+use crate::simulation::map::MapSource;
+use crate::simulation::server_sim::game_mode::MatchOutcome;
+use crate::simulation::server_sim::game_mode::battle_royale::BattleRoyaleZone;
+use crate::simulation::server_sim::game_mode::capture_the_flag::FlagEvent;
+use crate::simulation::server_sim::game_mode::king_of_the_hill::ControlPoints;
+use crate::simulation::server_sim::game_mode::elimination::RoundState;
+use crate::simulation::server_sim::game_mode::survival::WaveState;
 use crate::simulation::PlayerData;
+use crate::simulation::server_sim::weapon::WeaponStatus;
+use crate::simulation::server_sim::skill::{ActiveShields, SkillStatus};
+use crate::simulation::team::Team;
 
 pub type PlayerId = u64;
 
@@ -18,7 +28,22 @@ pub enum ServerMessage {
     PhysObjUpdate { objects: HashMap<ObjectId, PhysicsObjData> },
     PlayerDataUpdate { player_id: PlayerId, data: PlayerData }, //TODO find a better way to update K/D count
     HealthUpdate { object_id: ObjectId, health: f32, max_health: f32 },
-    TurretRotationUpdate { turrets: HashMap<ObjectId, TurretRotationData> } //TODO find a better way
+    TurretRotationUpdate { turrets: HashMap<ObjectId, TurretRotationData> }, //TODO find a better way
+    MapInit { source: MapSource },
+    ZoneUpdate { zone: BattleRoyaleZone },
+    GameModeInit { name: String, teams: Vec<Team> },
+    MatchEnd { outcome: MatchOutcome },
+    FlagFeed { event: FlagEvent },
+    ControlPointsUpdate { control_points: ControlPoints },
+    RoundUpdate { rounds: RoundState },
+    WaveUpdate { waves: WaveState },
+    BulletBounce { object_id: ObjectId, position: Vec2, velocity: Vec2 },
+    Explosion { position: Vec2, radius: f32 },
+    Beam { start: Vec2, end: Vec2 },
+    WeaponStatusUpdate { status: WeaponStatus },
+    SkillStatusUpdate { status: SkillStatus },
+    ShieldsUpdate { shields: ActiveShields },
+    ShieldBlock { position: Vec2 },
 }
 
 #[derive(Debug, Serialize, Deserialize, Clone)]
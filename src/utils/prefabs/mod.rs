@@ -2,19 +2,30 @@ use bevy::prelude::{Bundle, Camera2dBundle, default, OrthographicProjection, Spa
 use bevy::core::Name;
 use bevy::sprite::Anchor;
 use bevy::math::Vec2;
-use bevy_rapier2d::dynamics::{Damping, LockedAxes, RigidBody, Velocity};
-use bevy_rapier2d::geometry::Collider;
+use bevy_rapier2d::dynamics::{Damping, ExternalImpulse, LockedAxes, RigidBody, Velocity};
+use bevy_rapier2d::geometry::{ActiveEvents, Collider, CollisionGroups, Group, Sensor};
 use crate::asset_loader::components::SpriteEnum;
 use crate::display::camera::MainCamera;
-use crate::simulation::server_sim::player::{Health, Player, PlayerTurret};
+use crate::simulation::server_sim::leveling::AppliedUpgrades;
+use crate::simulation::server_sim::pickup::{ActiveSpawner, Buffs, PickupSpawner};
+use crate::simulation::server_sim::skill::SkillLoadout;
+use crate::simulation::server_sim::ai::{AiTank, EnteringArena, entering_collision_groups};
+use crate::simulation::server_sim::player::{DamageTaken, Health, Player, PlayerInput, PlayerTurret};
 use crate::display::sprite_updater::{AutoSorted, BACKGROUND_LAYER, CAMERA_LAYER, PLAYER_LAYER};
 use crate::simulation::server_sim::spawn::SpawnPoint;
+use crate::simulation::Object;
+use crate::simulation::server_sim::game_mode::capture_the_flag::Flag;
+use crate::simulation::server_sim::game_mode::king_of_the_hill::ControlPointSensor;
+use crate::simulation::team::Team;
 
 const TREE_ANCHOR: [f32; 2] = [0., -0.22];
 const TREE_TRUNK_ANCHOR: [f32; 2] = [0., -0.375];
 const _TURRET_ANCHOR: [f32; 2] = [-0.045, 0.15];
 const TURRET_POSITION: [f32; 2] = [-7., 27.];
-const TANK_COLLIDER_RADIUS: f32 = 45.;
+pub const TANK_COLLIDER_RADIUS: f32 = 45.;
+pub const TREE_COLLIDER_RADIUS: f32 = 100.;
+/// Trees and walls, so tanks can be let through the edge of the map.
+pub const MAP_COLLISION_GROUP: Group = Group::GROUP_2;
 
 pub fn default_camera() -> impl Bundle {
     (
@@ -47,7 +58,8 @@ pub fn tree() -> impl Bundle {
             ..default()
         },
         TransformBundle::default(),
-        Collider::ball(100.),
+        Collider::ball(TREE_COLLIDER_RADIUS),
+        CollisionGroups::new(MAP_COLLISION_GROUP, Group::ALL),
     )
 }
 
@@ -73,6 +85,16 @@ pub fn tree_leaves() -> impl Bundle {
     )
 }
 
+pub fn arena_walls(bounds: &[Vec2]) -> impl Bundle {
+    let vertices = bounds.iter().chain(bounds.first()).copied().collect();
+    (
+        Name::new("Arena Walls"),
+        TransformBundle::default(),
+        Collider::polyline(vertices, None),
+        CollisionGroups::new(MAP_COLLISION_GROUP, Group::ALL),
+    )
+}
+
 pub fn spawn_point(position: Vec2) -> impl Bundle {
     (
         TransformBundle::from_transform(Transform::from_xyz(position.x, position.y, 0.)),
@@ -80,6 +102,39 @@ pub fn spawn_point(position: Vec2) -> impl Bundle {
     )
 }
 
+pub fn flag(team: Team, base: Vec2) -> impl Bundle {
+    (
+        Name::new(format!("{} Flag", team.name())),
+        AutoSorted,
+        Flag::new(team, base),
+        team.flag_sprite(),
+        Object::new(),
+        TransformBundle::from_transform(Transform::from_translation(base.extend(PLAYER_LAYER))),
+    )
+}
+
+/// Only the server puts pickups down, clients are sent them like any other object.
+pub fn pickup_spawner(spawner: PickupSpawner) -> impl Bundle {
+    (
+        Name::new("Pickup Spawner"),
+        ActiveSpawner::new(spawner),
+    )
+}
+
+pub fn control_point_sensor(index: usize, center: Vec2, radius: f32) -> impl Bundle {
+    (
+        Name::new("Control Point"),
+        ControlPointSensor {
+            index,
+            occupants: default(),
+        },
+        TransformBundle::from_transform(Transform::from_translation(center.extend(0.))),
+        Collider::ball(radius),
+        Sensor,
+        ActiveEvents::COLLISION_EVENTS,
+    )
+}
+
 pub fn get_player_bundle(id: u64, position: Option<Vec2>) -> impl Bundle {
     let position = match position {
         Some(position) => position,
@@ -90,7 +145,7 @@ pub fn get_player_bundle(id: u64, position: Option<Vec2>) -> impl Bundle {
         Name::from(format!("Player {id}")),
         AutoSorted,
         Player::new(id),
-        Health::default(),
+        (Health::default(), DamageTaken::default()),
         SpriteEnum::TankDefault,
         SpatialBundle {
             transform: Transform {
@@ -103,6 +158,10 @@ pub fn get_player_bundle(id: u64, position: Option<Vec2>) -> impl Bundle {
         Collider::ball(TANK_COLLIDER_RADIUS),
         LockedAxes::ROTATION_LOCKED,
         Velocity::default(),
+        ExternalImpulse::default(),
+        SkillLoadout::default(),
+        AppliedUpgrades::default(),
+        Buffs::default(),
         Damping {
             linear_damping: 5.,
             ..default()
@@ -110,10 +169,13 @@ pub fn get_player_bundle(id: u64, position: Option<Vec2>) -> impl Bundle {
     )
 }
 
-pub fn get_turret_bundle() -> impl Bundle {
+pub fn get_turret_bundle(weapon: &str) -> impl Bundle {
     (
         Name::from("Turret"),
-        PlayerTurret::default(),
+        PlayerTurret {
+            weapon: weapon.to_owned(),
+            ..default()
+        },
         SpriteEnum::TankDefaultTurret,
         Sprite {
             anchor: Anchor::Custom(Vec2::from(_TURRET_ANCHOR)),
@@ -125,3 +187,14 @@ pub fn get_turret_bundle() -> impl Bundle {
         }
     )
 }
+
+/// Goes on top of `get_player_bundle` to hand the tank to the AI. It starts outside the map.
+pub fn get_ai_tank_bundle(health: f32, fire_interval: f32) -> impl Bundle {
+    (
+        AiTank::new(fire_interval),
+        EnteringArena,
+        entering_collision_groups(),
+        PlayerInput::default(),
+        Health::new(health),
+    )
+}
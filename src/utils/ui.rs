@@ -14,4 +14,18 @@ pub static DEFAULT_FRAME: Lazy<Frame> = Lazy::new(||
         fill: Visuals::default().window_fill(),
         ..default()
     }
-);
\ No newline at end of file
+);
+
+/// Formats seconds as `m:ss`, or `h:mm:ss` past an hour.
+pub fn format_time(seconds: f32) -> String {
+    let total_seconds = seconds.max(0.).floor() as u32;
+    let seconds = total_seconds % 60;
+    let minutes = (total_seconds / 60) % 60;
+    let hours = total_seconds / 60 / 60;
+
+    if hours > 0 {
+        format!("{}:{:02}:{:02}", hours, minutes, seconds)
+    } else {
+        format!("{}:{:02}", minutes, seconds)
+    }
+}